@@ -1,8 +1,9 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 fn main() {
     println!("cargo::rustc-check-cfg=cfg(flatpak_build)");
+    println!("cargo::rustc-check-cfg=cfg(gamedb_public_key)");
 
     #[rustfmt::skip]
     let config = slint_build::CompilerConfiguration::new()
@@ -18,6 +19,11 @@ fn main() {
         }
     }
 
+    println!("cargo::rerun-if-changed=resources/gamedb.pub");
+    if std::path::Path::new("resources/gamedb.pub").exists() {
+        println!("cargo::rustc-cfg=gamedb_public_key");
+    }
+
     #[cfg(all(windows, not(debug_assertions)))]
     {
         let mut res = winresource::WindowsResource::new();
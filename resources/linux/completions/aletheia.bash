@@ -1,12 +1,26 @@
-# SPDX-FileCopyrightText: 2025 Spencer
+# SPDX-FileCopyrightText: 2025-2026 Spencer
 # SPDX-License-Identifier: CC0-1.0
 
 _aletheia() {
-  local commands="backup restore update update_gamedb update_custom_gamedbs"
+  local commands="backup gamedb near_misses restore update update_gamedb update_custom_gamedbs"
   local input="${COMP_WORDS[COMP_CWORD]}"
 
   if [[ ${COMP_CWORD} -eq 1 ]]; then
     COMPREPLY=($(compgen -W "$commands" -- "$input"))
+  elif [[ ${COMP_WORDS[1]} == gamedb && ${COMP_CWORD} -eq 2 ]]; then
+    COMPREPLY=($(compgen -W "import lint rollback sources" -- "$input"))
+  elif [[ $input == -* ]]; then
+    local flags="--json"
+
+    case "${COMP_WORDS[1]}" in
+      backup) flags+=" --jobs --orphans --paranoid --verify" ;;
+      gamedb) [[ ${COMP_WORDS[2]} == sources ]] && flags+=" --conflicts" ;;
+      restore) flags+=" --from-steam-account --prefix --steam-account" ;;
+    esac
+
+    COMPREPLY=($(compgen -W "$flags" -- "$input"))
+  elif [[ ${COMP_WORDS[1]} == gamedb && ${COMP_WORDS[2]} =~ ^(import|lint)$ ]]; then
+    COMPREPLY=($(compgen -f -- "$input"))
   fi
 }
 
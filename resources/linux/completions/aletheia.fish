@@ -1,10 +1,27 @@
-# SPDX-FileCopyrightText: 2025 Spencer
+# SPDX-FileCopyrightText: 2025-2026 Spencer
 # SPDX-License-Identifier: CC0-1.0
 
-set -l commands backup restore update update_gamedb update_custom_gamedbs
+set -l commands backup gamedb near_misses restore update update_gamedb update_custom_gamedbs
 
 complete -c aletheia -n "not __fish_seen_subcommand_from $commands" -a "backup" -d "Create a backup"
+complete -c aletheia -n "not __fish_seen_subcommand_from $commands" -a "gamedb" -d "Manage game databases"
+complete -c aletheia -n "not __fish_seen_subcommand_from $commands" -a "near_misses" -d "List installed games that almost matched the GameDB"
 complete -c aletheia -n "not __fish_seen_subcommand_from $commands" -a "restore" -d "Restore from backup"
 complete -c aletheia -n "not __fish_seen_subcommand_from $commands" -a "update" -d "Update the application"
 complete -c aletheia -n "not __fish_seen_subcommand_from $commands" -a "update_gamedb" -d "Update GameDB"
 complete -c aletheia -n "not __fish_seen_subcommand_from $commands" -a "update_custom_gamedbs" -d "Update custom GameDBs"
+
+complete -c aletheia -n "__fish_seen_subcommand_from gamedb; and __fish_is_nth_token 2" -f -a "import" -d "Replace the GameDB with a copy from a file"
+complete -c aletheia -n "__fish_seen_subcommand_from gamedb; and __fish_is_nth_token 2" -f -a "lint" -d "Check game databases for mistakes"
+complete -c aletheia -n "__fish_seen_subcommand_from gamedb; and __fish_is_nth_token 2" -f -a "rollback" -d "Go back to the previous GameDB"
+complete -c aletheia -n "__fish_seen_subcommand_from gamedb; and __fish_is_nth_token 2" -f -a "sources" -d "Show which databases each game comes from"
+
+complete -c aletheia -n "__fish_seen_subcommand_from $commands" -l json -d "Print results as JSON lines"
+complete -c aletheia -n "__fish_seen_subcommand_from backup" -l jobs -x -d "Number of games to back up at once"
+complete -c aletheia -n "__fish_seen_subcommand_from backup" -l orphans -d "Also back up saves of uninstalled games"
+complete -c aletheia -n "__fish_seen_subcommand_from backup" -l paranoid -d "Hash every file instead of trusting the previous backup"
+complete -c aletheia -n "__fish_seen_subcommand_from backup" -l verify -d "Verify the new archive before replacing the previous backup"
+complete -c aletheia -n "__fish_seen_subcommand_from sources" -l conflicts -d "Only show games defined by more than one database"
+complete -c aletheia -n "__fish_seen_subcommand_from restore" -l from-steam-account -x -d "Steam ID3 or ID64 of the account to restore saves from"
+complete -c aletheia -n "__fish_seen_subcommand_from restore" -l prefix -x -a "(__fish_complete_directories)" -d "Wine prefix to restore uninstalled games into"
+complete -c aletheia -n "__fish_seen_subcommand_from restore" -l steam-account -x -d "Steam ID3 or ID64 of the account to restore saves to"
@@ -2,14 +2,24 @@
 // SPDX-License-Identifier: AGPL-3.0-only
 
 use crate::file::hash_bytes;
+use blake3::Hasher;
 use serde::{Deserialize, Serialize};
 use std::fs::{File, metadata};
 use std::io::{Read, Seek, SeekFrom, Write, copy};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
+#[cfg(unix)]
+use std::ffi::OsStr;
+#[cfg(windows)]
+use std::ffi::OsString;
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+#[cfg(windows)]
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
 const MAGIC: &[u8; 8] = b"ALETHEIA";
-const VERSION: u8 = 1;
+pub const VERSION: u8 = 3;
 const MIN_HEADER_SIZE: usize = 34;
 
 #[derive(Debug, thiserror::Error)]
@@ -36,6 +46,17 @@ pub enum CompressionType {
     Zstd = 1
 }
 
+/// How the bytes of a shrunk path were encoded, which depends on the OS that made the backup.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum PathEncoding {
+    /// Bytes of a Unix path as the OS returned them, which may not be valid UTF-8.
+    Unix = 0,
+    /// UTF-8, from archives made before the encoding was recorded.
+    Utf8 = 1,
+    /// WTF-8 of a Windows path, UTF-8 that also allows the unpaired surrogates Windows permits in file names.
+    Wtf8 = 2
+}
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct FileEntry {
     pub checksum: String,
@@ -43,7 +64,179 @@ pub struct FileEntry {
     data_offset: u64,
     data_size: u64,
     pub modified: SystemTime,
-    pub shrunk_path: String
+    pub path_encoding: PathEncoding,
+    /// Bytes of the shrunk path in `path_encoding`, which may not be valid UTF-8.
+    raw_path: Vec<u8>,
+    /// Shrunk path for display, with anything that isn't valid UTF-8 replaced.
+    pub shrunk_path: String,
+    pub size: u64,
+    /// Steam account ID3 the path was expanded for, if it depends on the account.
+    pub steam_account: Option<String>
+}
+
+/// Index entry of version 1 archives, which didn't record the uncompressed size.
+#[derive(Deserialize)]
+struct LegacyFileEntry {
+    checksum: String,
+    compression: CompressionType,
+    data_offset: u64,
+    data_size: u64,
+    modified: SystemTime,
+    shrunk_path: String
+}
+
+/// Index entry of version 2 archives, which only stored paths as UTF-8.
+#[derive(Deserialize)]
+struct V2FileEntry {
+    checksum: String,
+    compression: CompressionType,
+    data_offset: u64,
+    data_size: u64,
+    modified: SystemTime,
+    shrunk_path: String,
+    size: u64
+}
+
+impl From<LegacyFileEntry> for FileEntry {
+    fn from(entry: LegacyFileEntry) -> Self {
+        Self {
+            checksum: entry.checksum,
+            compression: entry.compression,
+            data_offset: entry.data_offset,
+            data_size: entry.data_size,
+            modified: entry.modified,
+            path_encoding: PathEncoding::Utf8,
+            raw_path: entry.shrunk_path.clone().into_bytes(),
+            shrunk_path: entry.shrunk_path,
+            size: 0,
+            steam_account: None
+        }
+    }
+}
+
+impl From<V2FileEntry> for FileEntry {
+    fn from(entry: V2FileEntry) -> Self {
+        Self {
+            checksum: entry.checksum,
+            compression: entry.compression,
+            data_offset: entry.data_offset,
+            data_size: entry.data_size,
+            modified: entry.modified,
+            path_encoding: PathEncoding::Utf8,
+            raw_path: entry.shrunk_path.clone().into_bytes(),
+            shrunk_path: entry.shrunk_path,
+            size: entry.size,
+            steam_account: None
+        }
+    }
+}
+
+impl FileEntry {
+    /// Shrunk path with the exact bytes it was backed up with.
+    pub fn path(&self) -> PathBuf {
+        decode_path(self.path_encoding, &self.raw_path)
+    }
+
+    fn is(&self, shrunk_path: &Path, steam_account: Option<&str>) -> bool {
+        self.path() == shrunk_path && self.steam_account.as_deref() == steam_account
+    }
+}
+
+/// Steam accounts with saves in an archive, in ascending order.
+pub fn steam_accounts(files: &[FileEntry]) -> Vec<&str> {
+    let mut accounts: Vec<&str> = files.iter().filter_map(|entry| entry.steam_account.as_deref()).collect();
+    accounts.sort_unstable();
+    accounts.dedup();
+    accounts
+}
+
+#[cfg(unix)]
+fn encode_path(path: &Path) -> (PathEncoding, Vec<u8>) {
+    (PathEncoding::Unix, path.as_os_str().as_bytes().to_vec())
+}
+
+#[cfg(windows)]
+#[expect(clippy::cast_possible_truncation, reason = "Each byte is masked to fit")]
+fn encode_path(path: &Path) -> (PathEncoding, Vec<u8>) {
+    let mut bytes = vec![];
+
+    for unit in char::decode_utf16(path.as_os_str().encode_wide()) {
+        match unit {
+            Ok(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            // Unpaired surrogates are encoded like any other code point, which UTF-8 itself doesn't allow
+            Err(e) => {
+                let surrogate = e.unpaired_surrogate();
+                bytes.extend([
+                    0xE0 | (surrogate >> 12) as u8,
+                    0x80 | ((surrogate >> 6) & 0x3F) as u8,
+                    0x80 | (surrogate & 0x3F) as u8
+                ]);
+            }
+        }
+    }
+
+    (PathEncoding::Wtf8, bytes)
+}
+
+/// Unix paths are bytes, so paths in any encoding are used as they are.
+#[cfg(unix)]
+fn decode_path(_encoding: PathEncoding, bytes: &[u8]) -> PathBuf {
+    PathBuf::from(OsStr::from_bytes(bytes))
+}
+
+#[cfg(windows)]
+fn decode_path(encoding: PathEncoding, bytes: &[u8]) -> PathBuf {
+    if encoding == PathEncoding::Unix {
+        return PathBuf::from(String::from_utf8_lossy(bytes).into_owned());
+    }
+
+    let mut wide = vec![];
+    let mut rest = bytes;
+
+    while !rest.is_empty() {
+        let (valid, invalid) = match std::str::from_utf8(rest) {
+            Ok(valid) => (valid, &[][..]),
+            Err(e) => (std::str::from_utf8(&rest[..e.valid_up_to()]).unwrap(), &rest[e.valid_up_to()..])
+        };
+
+        wide.extend(valid.encode_utf16());
+        rest = match *invalid {
+            [] => invalid,
+            [0xED, second @ 0xA0..=0xBF, third @ 0x80..=0xBF, ..] => {
+                wide.push(0xD000 | (u16::from(second & 0x3F) << 6) | u16::from(third & 0x3F));
+                &invalid[3..]
+            }
+            _ => {
+                wide.push(0xFFFD);
+                &invalid[1..]
+            }
+        };
+    }
+
+    PathBuf::from(OsString::from_wide(&wide))
+}
+
+/// Reader that hashes and counts the bytes read through it, so the checksum always matches the data that was stored.
+struct HashingReader<R> {
+    hasher: Hasher,
+    inner: R,
+    size: u64
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        self.size += read as u64;
+        Ok(read)
+    }
+}
+
+/// Header and index of an archive, read without touching the file data.
+pub struct ArchiveIndex {
+    pub files: Vec<FileEntry>,
+    pub game: String,
+    pub version: u8
 }
 
 pub struct ArchiveWriter {
@@ -63,23 +256,30 @@ impl ArchiveWriter {
         Self { files: vec![], game, path: path.to_path_buf() }
     }
 
-    pub fn add_file(&mut self, shrunk_path: &str, source: &Path, hash: String) {
+    pub fn add_file(&mut self, shrunk_path: &Path, steam_account: Option<&str>, source: &Path) {
+        let (path_encoding, raw_path) = encode_path(shrunk_path);
+
         self.files.push((
             FileEntry {
-                checksum: hash,
+                checksum: String::new(),
                 compression: CompressionType::None,
                 data_offset: 0,
                 data_size: 0,
                 modified: SystemTime::UNIX_EPOCH,
-                shrunk_path: shrunk_path.to_owned()
+                path_encoding,
+                raw_path,
+                shrunk_path: shrunk_path.display().to_string(),
+                size: 0,
+                steam_account: steam_account.map(ToOwned::to_owned)
             },
             source.to_path_buf()
         ));
     }
 
-    pub fn finalize(self) -> Result<()> {
+    /// Writes the archive, hashing each file while it's copied in. Returns the entries that were written.
+    pub fn finalize(self) -> Result<Vec<FileEntry>> {
         if self.files.is_empty() {
-            return Ok(());
+            return Ok(vec![]);
         }
 
         let mut file = File::create(&self.path)?;
@@ -91,11 +291,10 @@ impl ArchiveWriter {
 
         for (mut entry, source_path) in self.files {
             let metadata = metadata(&source_path)?;
-            let uncompressed_size = metadata.len();
             let start_pos = file.stream_position()?;
 
-            let mut source = File::open(&source_path)?;
-            let compression = if uncompressed_size >= 1024 {
+            let mut source = HashingReader { hasher: Hasher::new(), inner: File::open(&source_path)?, size: 0 };
+            let compression = if metadata.len() >= 1024 {
                 let mut encoder = zstd::Encoder::new(&mut file, 3)?;
                 copy(&mut source, &mut encoder)?;
                 encoder.finish()?;
@@ -108,10 +307,12 @@ impl ArchiveWriter {
             let end_pos = file.stream_position()?;
             let data_size = end_pos - start_pos;
 
+            entry.checksum = source.hasher.finalize().to_hex().to_string();
             entry.compression = compression;
             entry.data_offset = next_offset;
             entry.data_size = data_size;
             entry.modified = metadata.modified()?;
+            entry.size = source.size;
 
             entries.push(entry);
             next_offset += data_size;
@@ -125,7 +326,7 @@ impl ArchiveWriter {
 
         Self::write_header(&mut file, index_offset, index_size, &self.game)?;
 
-        Ok(())
+        Ok(entries)
     }
 
     fn write_header(file: &mut File, index_offset: u64, index_size: u64, game: &str) -> Result<()> {
@@ -152,7 +353,29 @@ impl ArchiveWriter {
 impl ArchiveReader {
     pub fn open(path: &Path) -> Result<Self> {
         let mut file = File::open(path)?;
+        let ArchiveIndex { mut files, game, version } = Self::read_index_from(&mut file)?;
+
+        for entry in &mut files {
+            let data = Self::decompress(&mut file, entry)?;
+            let checksum = hash_bytes(&data);
+            if checksum != entry.checksum {
+                return Err(Error::ChecksumMismatch(entry.checksum.clone(), checksum));
+            }
+
+            if version < 2 {
+                entry.size = data.len() as u64;
+            }
+        }
+
+        Ok(Self { file, files, game })
+    }
+
+    /// Reads the header and index of an archive without decompressing or verifying any file data.
+    pub fn read_index(path: &Path) -> Result<ArchiveIndex> {
+        Self::read_index_from(&mut File::open(path)?)
+    }
 
+    fn read_index_from(file: &mut File) -> Result<ArchiveIndex> {
         let mut magic = [0u8; 8];
         file.read_exact(&mut magic)?;
         if &magic != MAGIC {
@@ -161,8 +384,9 @@ impl ArchiveReader {
 
         let mut version = [0u8; 1];
         file.read_exact(&mut version)?;
-        if version[0] != VERSION {
-            return Err(Error::UnsupportedVersion(version[0]));
+        let version = version[0];
+        if version == 0 || version > VERSION {
+            return Err(Error::UnsupportedVersion(version));
         }
 
         let mut created = [0u8; 8];
@@ -174,7 +398,7 @@ impl ArchiveReader {
 
         let mut game_name_bytes = vec![0u8; game_name_len as usize];
         file.read_exact(&mut game_name_bytes)?;
-        let game = String::from_utf8(game_name_bytes).unwrap();
+        let game = String::from_utf8(game_name_bytes).map_err(|_| Error::InvalidArchive)?;
 
         let mut index_offset_bytes = [0u8; 8];
         file.read_exact(&mut index_offset_bytes)?;
@@ -185,19 +409,16 @@ impl ArchiveReader {
         let index_size = u64::from_le_bytes(index_size_bytes);
 
         file.seek(SeekFrom::Start(index_offset))?;
-        let mut index_bytes = vec![0u8; index_size as usize];
+        let mut index_bytes = vec![0u8; usize::try_from(index_size).map_err(|_| Error::InvalidArchive)?];
         file.read_exact(&mut index_bytes)?;
-        let files: Vec<FileEntry> = postcard::from_bytes(&index_bytes)?;
 
-        for entry in &files {
-            let data = Self::decompress(&mut file, entry)?;
-            let checksum = hash_bytes(&data);
-            if checksum != entry.checksum {
-                return Err(Error::ChecksumMismatch(entry.checksum.clone(), checksum));
-            }
-        }
+        let files: Vec<FileEntry> = match version {
+            1 => postcard::from_bytes::<Vec<LegacyFileEntry>>(&index_bytes)?.into_iter().map(Into::into).collect(),
+            2 => postcard::from_bytes::<Vec<V2FileEntry>>(&index_bytes)?.into_iter().map(Into::into).collect(),
+            _ => postcard::from_bytes(&index_bytes)?
+        };
 
-        Ok(Self { file, files, game })
+        Ok(ArchiveIndex { files, game, version })
     }
 
     fn decompress(file: &mut File, entry: &FileEntry) -> Result<Vec<u8>> {
@@ -211,16 +432,19 @@ impl ArchiveReader {
         }
     }
 
-    pub fn extract_file(&mut self, shrunk_path: &str, dest: &Path) -> Result<()> {
-        let entry =
-            self.files.iter().find(|e| e.shrunk_path == shrunk_path).ok_or_else(|| Error::FileNotFound(shrunk_path.to_owned()))?;
+    pub fn extract_file(&mut self, shrunk_path: &Path, steam_account: Option<&str>, dest: &Path) -> Result<u64> {
+        let entry = self
+            .files
+            .iter()
+            .find(|e| e.is(shrunk_path, steam_account))
+            .ok_or_else(|| Error::FileNotFound(shrunk_path.display().to_string()))?;
 
         let data = Self::decompress(&mut self.file, entry)?;
         let mut output = File::create(dest)?;
         output.write_all(&data)?;
         output.set_modified(entry.modified)?;
 
-        Ok(())
+        Ok(data.len() as u64)
     }
 }
 
@@ -241,20 +465,63 @@ mod tests {
         drop(f);
 
         let mut writer = ArchiveWriter::new("Test Game".into(), &archive_path);
-        writer.add_file("test.txt".into(), &test_file, "288a86a79f20a3d6dccdca7713beaed178798296bdfa7913fa2a62d9727bf8f8".to_string());
+        writer.add_file(Path::new("test.txt"), None, &test_file);
         writer.finalize().unwrap();
 
+        let index = ArchiveReader::read_index(&archive_path).unwrap();
+        assert_eq!(index.version, VERSION);
+        assert_eq!(index.files[0].size, 13);
+
         let mut reader = ArchiveReader::open(&archive_path).unwrap();
         assert_eq!(&reader.game, "Test Game");
         assert_eq!(reader.files.len(), 1);
         assert_eq!(reader.files[0].checksum, "288a86a79f20a3d6dccdca7713beaed178798296bdfa7913fa2a62d9727bf8f8");
 
         let extract_path = temp.join("extracted.txt");
-        reader.extract_file("test.txt", &extract_path).unwrap();
+        reader.extract_file(Path::new("test.txt"), None, &extract_path).unwrap();
 
         let content = std::fs::read_to_string(&extract_path).unwrap();
         assert_eq!(content, "Hello, World!");
 
         std::fs::remove_dir_all("tests").unwrap();
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_paths() {
+        std::fs::create_dir_all("tests_non_utf8").unwrap();
+
+        let temp = Path::new("tests_non_utf8");
+        let archive_path = temp.join("test.aletheia");
+        let source = temp.join("save.dat");
+        let shrunk_path = Path::new(OsStr::from_bytes(b"{Home}/\x83Z\x81[\x83u.sav"));
+        std::fs::write(&source, b"Save").unwrap();
+
+        let mut writer = ArchiveWriter::new("Test Game".into(), &archive_path);
+        writer.add_file(shrunk_path, Some("22202"), &source);
+        writer.finalize().unwrap();
+
+        let mut reader = ArchiveReader::open(&archive_path).unwrap();
+        assert_eq!(reader.files[0].path_encoding, PathEncoding::Unix);
+        assert_eq!(reader.files[0].path(), shrunk_path);
+        assert_eq!(reader.files[0].shrunk_path, "{Home}/\u{FFFD}Z\u{FFFD}[\u{FFFD}u.sav");
+        assert_eq!(steam_accounts(&reader.files), ["22202"]);
+        assert!(reader.extract_file(shrunk_path, None, &temp.join("extracted.dat")).is_err());
+        assert_eq!(reader.extract_file(shrunk_path, Some("22202"), &temp.join("extracted.dat")).unwrap(), 4);
+
+        std::fs::remove_dir_all("tests_non_utf8").unwrap();
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_wtf8_paths() {
+        let shrunk_path =
+            PathBuf::from(OsString::from_wide(&[0x7B, 0x48, 0x6F, 0x6D, 0x65, 0x7D, 0x2F, 0xD800, 0xE9, 0xD83D, 0xDE00]));
+        let (encoding, bytes) = encode_path(&shrunk_path);
+
+        assert_eq!(encoding, PathEncoding::Wtf8);
+        assert_eq!(bytes, b"{Home}/\xED\xA0\x80\xC3\xA9\xF0\x9F\x98\x80");
+        assert_eq!(decode_path(encoding, &bytes), shrunk_path);
+        assert_eq!(decode_path(PathEncoding::Unix, &bytes), Path::new("{Home}/\u{FFFD}\u{FFFD}\u{FFFD}\u{E9}\u{1F600}"));
+    }
 }
@@ -1,6 +1,8 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
+mod reporter;
 mod steam;
 
+pub use reporter::{Outcome, Reporter};
 pub use steam::ensure_steam_account_selected;
@@ -0,0 +1,237 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use crate::commands::Args;
+use crate::operations::{BackupReport, BackupResult, RestoreReport, RestoreResult};
+use serde::Serialize;
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Success,
+    PartialFailure,
+    Failure
+}
+
+impl From<Outcome> for ExitCode {
+    fn from(outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::Success => Self::SUCCESS,
+            Outcome::PartialFailure => Self::from(2),
+            Outcome::Failure => Self::FAILURE
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Success,
+    Unchanged,
+    Partial,
+    Failed
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Level {
+    Info,
+    Warning,
+    Error
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Event<'a> {
+    Progress {
+        operation: &'a str,
+        game: &'a str,
+        current: usize,
+        total: usize
+    },
+    Game {
+        operation: &'a str,
+        game: &'a str,
+        status: Status,
+        files: usize,
+        bytes: u64,
+        errors: Vec<String>,
+        duration_ms: u128,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        verified: Option<bool>
+    },
+    Message {
+        level: Level,
+        message: &'a str
+    },
+    Summary {
+        outcome: Outcome,
+        succeeded: usize,
+        partial: usize,
+        failed: usize
+    }
+}
+
+/// Prints command results either as human readable text or as newline-delimited JSON events, and tracks the overall outcome.
+pub struct Reporter {
+    json: bool,
+    succeeded: usize,
+    partial: usize,
+    failed: usize
+}
+
+impl Reporter {
+    pub fn new(args: &Args) -> Self {
+        Self { json: args.has_flag("json"), succeeded: 0, partial: 0, failed: 0 }
+    }
+
+    pub const fn is_json(&self) -> bool {
+        self.json
+    }
+
+    fn emit(event: &Event) {
+        println!("{}", serde_json::to_string(event).unwrap());
+    }
+
+    fn record(&mut self, status: Status) {
+        match status {
+            Status::Success | Status::Unchanged => self.succeeded += 1,
+            Status::Partial => self.partial += 1,
+            Status::Failed => self.failed += 1
+        }
+    }
+
+    pub fn progress(&self, operation: &str, game: &str, current: usize, total: usize) {
+        if self.json {
+            Self::emit(&Event::Progress { operation, game, current, total });
+        }
+    }
+
+    pub fn backup(&mut self, game: &str, result: &BackupResult<BackupReport>, elapsed: Duration) {
+        let verified = result.as_ref().is_ok_and(|report| report.verified);
+        let (status, files, bytes, errors) = match result {
+            Ok(report) => {
+                let errors: Vec<String> =
+                    report.skipped.iter().map(|skipped| format!("{}: {}", skipped.path.display(), skipped.reason)).collect();
+
+                let status = if !errors.is_empty() {
+                    Status::Partial
+                } else if report.changed {
+                    Status::Success
+                } else {
+                    Status::Unchanged
+                };
+
+                (status, report.files, report.bytes, errors)
+            }
+            Err(e) => (Status::Failed, 0, 0, vec![e.to_string()])
+        };
+
+        self.record(status);
+
+        if self.json {
+            Self::emit(&Event::Game {
+                operation: "backup",
+                game,
+                status,
+                files,
+                bytes,
+                errors,
+                duration_ms: elapsed.as_millis(),
+                verified: Some(verified)
+            });
+            return;
+        }
+
+        match status {
+            Status::Failed => eprintln!("Failed to backup {game}: {}", errors[0]),
+            Status::Partial => {
+                for error in &errors {
+                    eprintln!("{game}: skipped {error}");
+                }
+
+                println!("Backed up {game} with {} skipped files.", errors.len());
+            }
+            Status::Success if verified => println!("Backed up and verified {game}."),
+            Status::Success | Status::Unchanged => println!("Backed up {game}.")
+        }
+    }
+
+    pub fn restore(&mut self, game: &str, result: &RestoreResult<RestoreReport>, elapsed: Duration) {
+        let (status, files, bytes, errors) = match result {
+            Ok(report) if report.skipped.is_empty() => (Status::Success, report.files, report.bytes, vec![]),
+            Ok(report) => (Status::Partial, report.files, report.bytes, report.skipped.clone()),
+            Err(e) => (Status::Failed, 0, 0, vec![e.to_string()])
+        };
+
+        self.record(status);
+
+        if let Ok(RestoreReport { remapped_from: Some(source), .. }) = result {
+            self.warning(&format!("Saves of {game} were backed up from Steam account {source}, restoring them to another account."));
+        }
+
+        if self.json {
+            #[rustfmt::skip]
+            Self::emit(&Event::Game { operation: "restore", game, status, files, bytes, errors, duration_ms: elapsed.as_millis(), verified: None });
+        } else if status == Status::Failed {
+            eprintln!("Failed to restore {game}: {}", errors[0]);
+        } else if status == Status::Partial {
+            for error in &errors {
+                eprintln!("{game}: skipped {error}, it needs the game installed or a Wine prefix");
+            }
+
+            println!("Restored {game} with {} skipped files.", errors.len());
+        } else {
+            println!("Restored {game}.");
+        }
+    }
+
+    pub fn info(&self, message: &str) {
+        if self.json {
+            Self::emit(&Event::Message { level: Level::Info, message });
+        } else {
+            println!("{message}");
+        }
+    }
+
+    pub fn warning(&self, message: &str) {
+        if self.json {
+            Self::emit(&Event::Message { level: Level::Warning, message });
+        } else {
+            eprintln!("Warning: {message}");
+        }
+    }
+
+    pub fn success(&mut self, message: &str) {
+        self.succeeded += 1;
+        self.info(message);
+    }
+
+    pub fn failure(&mut self, message: &str) {
+        self.failed += 1;
+
+        if self.json {
+            Self::emit(&Event::Message { level: Level::Error, message });
+        } else {
+            eprintln!("{message}");
+        }
+    }
+
+    pub fn finish(self) -> Outcome {
+        let outcome = if self.failed == 0 && self.partial == 0 {
+            Outcome::Success
+        } else if self.succeeded == 0 && self.partial == 0 {
+            Outcome::Failure
+        } else {
+            Outcome::PartialFailure
+        };
+
+        if self.json {
+            Self::emit(&Event::Summary { outcome, succeeded: self.succeeded, partial: self.partial, failed: self.failed });
+        }
+
+        outcome
+    }
+}
@@ -1,10 +1,12 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 #[cfg(all(feature = "updater", not(debug_assertions)))]
 mod update;
 
 mod backup;
+mod gamedb;
+mod near_misses;
 mod restore;
 mod update_custom;
 mod update_gamedb;
@@ -13,10 +15,17 @@ mod update_gamedb;
 pub use update::Update;
 
 pub use backup::Backup;
+pub use gamedb::GameDb;
+pub use near_misses::NearMisses;
 pub use restore::Restore;
 pub use update_custom::UpdateCustom;
 pub use update_gamedb::UpdateGameDb;
 
+use crate::cli_helpers::Outcome;
+
+/// Flags that never take a value, so the argument following them is kept as a positional.
+const SWITCHES: &[&str] = &["conflicts", "json", "orphans", "paranoid", "verify"];
+
 pub struct Args {
     pub positional: Vec<String>,
     pub flags: Vec<Flag>
@@ -44,7 +53,9 @@ impl Args {
 
         while let Some(arg) = args.next() {
             if let Some(name) = arg.strip_prefix("--") {
-                if let Some(value) = args.next().filter(|next| !next.starts_with('-')) {
+                if SWITCHES.contains(&name) {
+                    flags.push(Flag::new(name));
+                } else if let Some(value) = args.next().filter(|next| !next.starts_with('-')) {
                     flags.push(Flag::with_value(name, value));
                 } else {
                     flags.push(Flag::new(name));
@@ -57,7 +68,6 @@ impl Args {
         Self { positional, flags }
     }
 
-    #[expect(unused, reason = "Will be used in the future")]
     pub fn has_flag(&self, name: &str) -> bool {
         self.flags.iter().any(|f| f.name == name)
     }
@@ -72,5 +82,5 @@ impl Args {
 }
 
 pub trait Command {
-    fn run(args: Args, config: &crate::config::Config);
+    fn run(args: Args, config: &crate::config::Config) -> Outcome;
 }
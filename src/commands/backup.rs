@@ -1,45 +1,68 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 use super::{Args, Command};
-use crate::cli_helpers::ensure_steam_account_selected;
+use crate::cli_helpers::{Outcome, Reporter};
 use crate::config::Config;
 use crate::gamedb;
 use crate::infer;
-use crate::operations::backup_game;
+use crate::operations::{backup_games, default_jobs};
+use crate::scanner::Game;
 
 pub struct Backup;
 
 impl Command for Backup {
-    fn run(args: Args, config: &Config) {
-        let game_db = gamedb::parse();
-        let installed_games = gamedb::get_installed_games();
+    fn run(args: Args, config: &Config) -> Outcome {
+        let mut reporter = Reporter::new(&args);
+        let overridden_config;
+        let config = if args.has_flag("verify") || args.has_flag("paranoid") {
+            overridden_config = Config {
+                paranoid_backups: config.paranoid_backups || args.has_flag("paranoid"),
+                verify_backups: config.verify_backups || args.has_flag("verify"),
+                ..config.clone()
+            };
+            &overridden_config
+        } else {
+            config
+        };
 
-        if config.steam_account_id.is_none() && installed_games.iter().any(|g| g.source == "Steam") {
-            ensure_steam_account_selected(config);
-        }
+        let game_db = gamedb::load(config);
+        let mut installed_games = gamedb::get_installed_games(config);
 
-        if let Some(launcher) = args.get_flag_value("infer") {
-            infer::backup(launcher, config);
-            return;
+        if args.has_flag("orphans") {
+            let orphaned_games = gamedb::get_orphaned_games(config, &installed_games);
+            installed_games.extend(orphaned_games);
         }
 
-        if args.positional.is_empty() {
-            for game in &installed_games {
-                if let Err(e) = backup_game(game, config, &game_db[&game.name]) {
-                    eprintln!("Failed to backup {}: {}", game.name, e);
-                } else {
-                    println!("Backed up {}.", game.name);
-                }
-            }
+        let games: Vec<Game> = if let Some(launcher) = args.get_flag_value("infer") {
+            let Some(game) = infer::get_game(launcher, config) else {
+                reporter.failure("Failed to infer the game being played.");
+                return reporter.finish();
+            };
+
+            vec![game]
         } else {
-            installed_games.iter().filter(|game| args.positional.contains(&game.name)).for_each(|game| {
-                if let Err(e) = backup_game(game, config, &game_db[&game.name]) {
-                    eprintln!("Failed to backup {}: {}", game.name, e);
-                } else {
-                    println!("Backed up {}.", game.name);
-                }
-            });
-        }
+            installed_games.into_iter().filter(|game| args.positional.is_empty() || args.positional.contains(&game.name)).collect()
+        };
+
+        let jobs = match args.get_flag_value("jobs").map(|jobs| jobs.parse::<usize>()) {
+            Some(Ok(jobs)) if jobs > 0 => jobs,
+            Some(_) => {
+                reporter.failure("--jobs expects a positive number.");
+                return reporter.finish();
+            }
+            None => default_jobs()
+        };
+
+        let total = games.len();
+        let mut completed = 0;
+
+        backup_games(&games, &game_db.entries, config, jobs, |game, result, elapsed| {
+            completed += 1;
+            reporter.progress("backup", &game.name, completed, total);
+            reporter.backup(&game.name, &result, elapsed);
+        });
+
+        reporter.finish()
     }
 }
@@ -0,0 +1,78 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use super::{Args, Command};
+use crate::cli_helpers::{Outcome, Reporter};
+use crate::config::Config;
+use crate::gamedb::{self, MergeModes, Severity};
+use std::path::Path;
+
+pub struct GameDb;
+
+impl Command for GameDb {
+    fn run(args: Args, config: &Config) -> Outcome {
+        let mut reporter = Reporter::new(&args);
+
+        match args.positional.first().map(String::as_str) {
+            Some("import") if args.positional.len() == 2 => match gamedb::import(Path::new(&args.positional[1])) {
+                Ok(()) => reporter.success("Successfully imported GameDB."),
+                Err(e) => reporter.failure(&format!("Error importing GameDB: {e}"))
+            },
+            Some("lint") => lint(&args.positional[1..], config, &mut reporter),
+            Some("rollback") => match gamedb::rollback() {
+                Ok(()) => reporter.success("Rolled back to the previous GameDB."),
+                Err(e) => reporter.failure(&format!("Error rolling back GameDB: {e}"))
+            },
+            Some("sources") => sources(config, args.has_flag("conflicts"), &mut reporter),
+            _ => reporter.failure("Usage: aletheia gamedb <import FILE | lint [FILE|DIRECTORY]... | rollback | sources [--conflicts]>")
+        }
+
+        reporter.finish()
+    }
+}
+
+fn lint(paths: &[String], config: &Config, reporter: &mut Reporter) {
+    let sources = match gamedb::lint_sources(config, paths) {
+        Ok(sources) => sources,
+        Err(e) => return reporter.failure(&format!("Failed to read GameDB: {e}"))
+    };
+
+    for (source, contents) in sources {
+        let problems = gamedb::lint(&contents);
+        let warnings = problems.iter().filter(|problem| problem.severity == Severity::Warning).count();
+
+        for problem in &problems {
+            match problem.severity {
+                Severity::Error => reporter.failure(&format!("{source}:{}: error: {}", problem.line, problem.message)),
+                Severity::Warning => reporter.info(&format!("{source}:{}: warning: {}", problem.line, problem.message))
+            }
+        }
+
+        if warnings == problems.len() {
+            reporter.success(&format!("{source}: no errors, {warnings} warnings."));
+        }
+    }
+}
+
+/// Reports which databases supplied the effective entry of each game.
+fn sources(config: &Config, conflicts_only: bool, reporter: &mut Reporter) {
+    let db = gamedb::load(config);
+    let mut games: Vec<_> = db.sources.iter().filter(|(_, contributions)| !conflicts_only || contributions.len() > 1).collect();
+    games.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (game, contributions) in &games {
+        let description = contributions
+            .iter()
+            .map(|contribution| match contribution.merge.as_ref().map(MergeModes::appended) {
+                None => contribution.source.clone(),
+                Some(appended) if appended.is_empty() => format!("replaced by {}", contribution.source),
+                Some(appended) => format!("{} appended by {}", appended.join(", "), contribution.source)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        reporter.info(&format!("{game}: {description}"));
+    }
+
+    reporter.success(&format!("Listed {} games.", games.len()));
+}
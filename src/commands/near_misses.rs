@@ -0,0 +1,23 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use super::{Args, Command};
+use crate::cli_helpers::{Outcome, Reporter};
+use crate::config::Config;
+use crate::gamedb;
+
+pub struct NearMisses;
+
+impl Command for NearMisses {
+    fn run(args: Args, config: &Config) -> Outcome {
+        let mut reporter = Reporter::new(&args);
+        let near_misses = gamedb::get_near_misses(config);
+
+        for near_miss in &near_misses {
+            reporter.info(&format!("{} ({}) almost matched {}.", near_miss.title, near_miss.source, near_miss.candidate));
+        }
+
+        reporter.success(&format!("Found {} near misses.", near_misses.len()));
+        reporter.finish()
+    }
+}
@@ -1,76 +1,145 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 use super::{Args, Command};
 use crate::archive::ArchiveReader;
-use crate::cli_helpers::ensure_steam_account_selected;
+use crate::cli_helpers::{Outcome, Reporter, ensure_steam_account_selected};
 use crate::config::Config;
 use crate::gamedb;
 use crate::infer;
 use crate::operations::restore_game;
+use crate::scanner::{Game, SteamScanner};
 use std::path::Path;
+use std::time::Instant;
+
+#[cfg(unix)]
+use std::path::PathBuf;
 
 pub struct Restore;
 
 impl Command for Restore {
-    fn run(args: Args, config: &Config) {
-        let installed_games = gamedb::get_installed_games();
+    fn run(args: Args, config: &Config) -> Outcome {
+        let mut reporter = Reporter::new(&args);
+        let installed_games = gamedb::get_installed_games(config);
+
+        let (steam_account, from_steam_account) =
+            match (Self::steam_account(&args, "steam-account"), Self::steam_account(&args, "from-steam-account")) {
+                (Ok(steam_account), Ok(from_steam_account)) => (steam_account, from_steam_account),
+                (Err(e), _) | (_, Err(e)) => {
+                    reporter.failure(&e);
+                    return reporter.finish();
+                }
+            };
 
-        if config.steam_account_id.is_none() && installed_games.iter().any(|g| g.source == "Steam") {
+        // JSON output is consumed by scripts, which can't answer the account prompt.
+        if steam_account.is_none()
+            && config.steam_account_id.is_none()
+            && !reporter.is_json()
+            && installed_games.iter().any(|g| g.source == "Steam")
+        {
             ensure_steam_account_selected(config);
         }
 
         if args.positional.len() == 1 && args.positional[0].ends_with(".aletheia") {
             let archive_path = Path::new(&args.positional[0]);
             if !archive_path.exists() {
-                eprintln!("Archive file not found: {}", archive_path.display());
-                return;
+                reporter.failure(&format!("Archive file not found: {}", archive_path.display()));
+                return reporter.finish();
             }
 
             let reader = match ArchiveReader::open(archive_path) {
                 Ok(r) => r,
                 Err(e) => {
-                    eprintln!("Failed to open archive: {e}");
-                    return;
+                    reporter.failure(&format!("Failed to open archive: {e}"));
+                    return reporter.finish();
                 }
             };
 
-            let Some(game) = installed_games.iter().find(|g| g.name == reader.game) else {
-                eprintln!("{} is not installed.", reader.game);
-                return;
-            };
-
-            println!("Restoring {}", reader.game);
+            let game = installed_games
+                .into_iter()
+                .find(|g| g.name == reader.game)
+                .unwrap_or_else(|| Self::uninstalled_game(&args, reader.game.clone()));
 
-            if let Err(e) = restore_game(game, config) {
-                eprintln!("Failed to restore {}: {e}", reader.game);
-            } else {
-                println!("Restored {}.", reader.game);
+            if !reporter.is_json() {
+                println!("Restoring {}", reader.game);
             }
 
-            return;
+            Self::restore(&mut reporter, &game, config, steam_account.as_deref(), from_steam_account.as_deref(), 1, 1);
+            return reporter.finish();
         }
 
         if !config.save_dir.exists() {
-            eprintln!("Backup directory doesn't exist.");
-            return;
+            reporter.failure("Backup directory doesn't exist.");
+            return reporter.finish();
         }
 
-        if let Some(launcher) = args.get_flag_value("infer") {
-            infer::restore(launcher, config);
-            return;
+        let games: Vec<Game> = if let Some(launcher) = args.get_flag_value("infer") {
+            let Some(game) = infer::get_game(launcher, config) else {
+                reporter.failure("Failed to infer the game being played.");
+                return reporter.finish();
+            };
+
+            vec![game]
+        } else {
+            // Games that aren't installed can still be restored by name, as long as their saves don't live in the installation directory.
+            let uninstalled_games: Vec<Game> = args
+                .positional
+                .iter()
+                .filter(|name| !installed_games.iter().any(|game| game.name == **name))
+                .map(|name| Self::uninstalled_game(&args, name.clone()))
+                .collect();
+
+            installed_games
+                .into_iter()
+                .filter(|game| args.positional.is_empty() || args.positional.contains(&game.name))
+                .chain(uninstalled_games)
+                .collect()
+        };
+
+        let total = games.len();
+
+        for (i, game) in games.iter().enumerate() {
+            Self::restore(&mut reporter, game, config, steam_account.as_deref(), from_steam_account.as_deref(), i + 1, total);
         }
 
-        for game in &installed_games {
-            if !args.positional.is_empty() && !args.positional.contains(&game.name) {
-                continue;
-            }
+        reporter.finish()
+    }
+}
 
-            if let Err(e) = restore_game(game, config) {
-                eprintln!("Failed to restore {}: {e}", game.name);
-            } else {
-                println!("Restored {}.", game.name);
-            }
+impl Restore {
+    /// Windows saves of uninstalled games are restored into the prefix given with `--prefix`, which is created if needed.
+    #[cfg(unix)]
+    fn uninstalled_game(args: &Args, name: String) -> Game {
+        Game { prefix: args.get_flag_value("prefix").map(PathBuf::from), ..Game::uninstalled(name) }
+    }
+
+    #[cfg(windows)]
+    fn uninstalled_game(_args: &Args, name: String) -> Game {
+        Game::uninstalled(name)
+    }
+
+    /// Steam ID3 of the account given with `--steam-account` or `--from-steam-account`, which also accept the ID64 shown by Steam.
+    fn steam_account(args: &Args, flag: &str) -> Result<Option<String>, String> {
+        let Some(id) = args.get_flag_value(flag) else {
+            return Ok(None);
+        };
+
+        match id.parse::<u64>() {
+            Ok(id64) if id64 >= SteamScanner::id3_to_id64(0) => Ok(Some(SteamScanner::id64_to_id3(id64).to_string())),
+            Ok(id3) if u32::try_from(id3).is_ok() => Ok(Some(id3.to_string())),
+            _ => Err(format!("--{flag} expects a Steam ID3 or ID64."))
         }
     }
+
+    fn restore(
+        reporter: &mut Reporter, game: &Game, config: &Config, steam_account: Option<&str>, from_steam_account: Option<&str>,
+        current: usize, total: usize
+    ) {
+        reporter.progress("restore", &game.name, current, total);
+
+        let start = Instant::now();
+        let result = restore_game(game, config, steam_account, from_steam_account);
+
+        reporter.restore(&game.name, &result, start.elapsed());
+    }
 }
@@ -1,19 +1,24 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 use super::{Args, Command};
+use crate::cli_helpers::{Outcome, Reporter};
 use crate::config::Config;
 use crate::updater;
 
 pub struct Update;
 
 impl Command for Update {
-    fn run(_args: Args, _config: &Config) {
-        match updater::check() {
+    fn run(args: Args, config: &Config) -> Outcome {
+        let mut reporter = Reporter::new(&args);
+
+        match updater::check(&config.http) {
             #[rustfmt::skip]
-            Ok(updater::UpdateStatus::Available(r)) => println!("Aletheia is out of date! You can download the newest release here: {}", r.url),
-            Ok(updater::UpdateStatus::UpToDate) => println!("Aletheia is already up to date."),
-            Err(e) => eprintln!("Error checking for updates: {e}")
+            Ok(updater::UpdateStatus::Available(r)) => reporter.success(&format!("Aletheia is out of date! You can download the newest release here: {}", r.url)),
+            Ok(updater::UpdateStatus::UpToDate) => reporter.success("Aletheia is already up to date."),
+            Err(e) => reporter.failure(&format!("Error checking for updates: {e}"))
         }
+
+        reporter.finish()
     }
 }
@@ -1,18 +1,23 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 use super::{Args, Command};
+use crate::cli_helpers::{Outcome, Reporter};
 use crate::config::Config;
 use crate::gamedb;
 
 pub struct UpdateCustom;
 
 impl Command for UpdateCustom {
-    fn run(_args: Args, config: &Config) {
+    fn run(args: Args, config: &Config) -> Outcome {
+        let mut reporter = Reporter::new(&args);
+
         match gamedb::update_custom(config) {
-            Ok(true) => println!("Successfully updated custom GameDBs."),
-            Ok(false) => println!("Custom GameDBs are already up to date."),
-            Err(e) => eprintln!("Error updating custom GameDBs: {e}")
+            Ok(true) => reporter.success("Successfully updated custom GameDBs."),
+            Ok(false) => reporter.success("Custom GameDBs are already up to date."),
+            Err(e) => reporter.failure(&format!("Error updating custom GameDBs: {e}"))
         }
+
+        reporter.finish()
     }
 }
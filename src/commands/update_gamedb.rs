@@ -1,18 +1,23 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 use super::{Args, Command};
+use crate::cli_helpers::{Outcome, Reporter};
 use crate::config::Config;
 use crate::gamedb;
 
 pub struct UpdateGameDb;
 
 impl Command for UpdateGameDb {
-    fn run(_args: Args, _config: &Config) {
-        match gamedb::update() {
-            Ok(true) => println!("Successfully updated GameDB."),
-            Ok(false) => println!("GameDB is already up to date."),
-            Err(e) => eprintln!("Error updating GameDB: {e}")
+    fn run(args: Args, config: &Config) -> Outcome {
+        let mut reporter = Reporter::new(&args);
+
+        match gamedb::update(config) {
+            Ok(true) => reporter.success("Successfully updated GameDB."),
+            Ok(false) => reporter.success("GameDB is already up to date."),
+            Err(e) => reporter.failure(&format!("Error updating GameDB: {e}"))
         }
+
+        reporter.finish()
     }
 }
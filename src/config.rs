@@ -1,7 +1,11 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 use crate::dirs;
+use crate::gamedb::GameDbEntry;
+use crate::hooks::Hooks;
+use crate::http::HttpConfig;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[cfg(not(target_os = "macos"))]
@@ -13,9 +17,19 @@ use std::fs::File;
 #[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct Config {
+    /// Custom game databases, merged in order so later ones take precedence over earlier ones and the official database.
     pub custom_databases: Vec<String>,
+    /// Public keys that custom databases must be signed with, by URL or path.
+    pub custom_database_keys: HashMap<String, String>,
+    /// Entries defined directly in the config, taking precedence over every game database.
+    pub custom_games: HashMap<String, GameDbEntry>,
+    pub game_hooks: HashMap<String, Hooks>,
+    pub hooks: Hooks,
+    pub http: HttpConfig,
+    pub paranoid_backups: bool,
     pub save_dir: PathBuf,
     pub steam_account_id: Option<String>,
+    pub verify_backups: bool,
     #[cfg(feature = "updater")]
     pub check_for_updates: bool
 }
@@ -115,8 +129,15 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             custom_databases: vec![],
+            custom_database_keys: HashMap::new(),
+            custom_games: HashMap::new(),
+            game_hooks: HashMap::new(),
+            hooks: Hooks::default(),
+            http: HttpConfig::default(),
+            paranoid_backups: false,
             save_dir: Self::get_save_dir(),
             steam_account_id: None,
+            verify_backups: false,
             #[cfg(feature = "updater")]
             check_for_updates: true
         }
@@ -1,13 +1,29 @@
 // SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
+mod pattern;
+
+pub use pattern::ReadError;
+
 use crate::scanner::SteamScanner;
+use glob::PatternError;
+use pattern::find_matches;
+use std::cmp::Reverse;
 use std::env::{home_dir, var_os};
+use std::ffi::OsStr;
 use std::fs::read_dir;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
-#[cfg(all(unix, not(target_os = "macos")))]
+#[cfg(unix)]
+use pattern::eq_ignore_case;
+#[cfg(unix)]
+use std::collections::BTreeMap;
+#[cfg(unix)]
 use std::ffi::OsString;
+#[cfg(unix)]
+use std::sync::Mutex;
+#[cfg(unix)]
+use std::time::SystemTime;
 
 #[cfg(target_os = "macos")]
 pub fn cache() -> PathBuf {
@@ -16,7 +32,7 @@ pub fn cache() -> PathBuf {
 
 #[cfg(all(unix, not(target_os = "macos")))]
 pub fn cache() -> PathBuf {
-    var_os("XDG_CACHE_HOME").map_or_else(|| home().join(".cache"), PathBuf::from).join("aletheia")
+    xdg_cache().join("aletheia")
 }
 
 #[cfg(windows)]
@@ -58,315 +74,423 @@ pub fn home() -> PathBuf {
     home_dir().unwrap()
 }
 
-fn expand_path_components(path: &Path, replacements: &[(&str, PathBuf)]) -> PathBuf {
-    let mut result = PathBuf::new();
-
-    for component in path.components() {
-        let component_os = component.as_os_str();
-        let mut replaced = false;
+#[cfg(windows)]
+fn program_data() -> PathBuf {
+    var_os("ProgramData").map_or_else(|| PathBuf::from("C:/ProgramData"), PathBuf::from)
+}
 
-        for (pattern, replacement) in replacements {
-            if component_os == *pattern {
-                result.push(replacement);
-                replaced = true;
-                break;
-            }
-        }
+#[cfg(windows)]
+fn public() -> PathBuf {
+    var_os("PUBLIC").map_or_else(|| PathBuf::from("C:/Users/Public"), PathBuf::from)
+}
 
-        if !replaced {
-            result.push(component);
-        }
-    }
+#[cfg(all(unix, not(target_os = "macos")))]
+fn xdg_cache() -> PathBuf {
+    var_os("XDG_CACHE_HOME").map_or_else(|| home().join(".cache"), PathBuf::from)
+}
 
-    result
+#[cfg(all(unix, not(target_os = "macos")))]
+fn xdg_state() -> PathBuf {
+    var_os("XDG_STATE_HOME").map_or_else(|| home().join(".local/state"), PathBuf::from)
 }
 
-fn shrink_path_components(path: &Path, replacements: &[(&str, PathBuf)]) -> PathBuf {
-    for (pattern, replacement) in replacements {
-        if let Ok(stripped) = path.strip_prefix(replacement) {
-            let mut new_path = PathBuf::from(pattern);
-            new_path.push(stripped);
+/// Name of the current user, or of their home directory when the environment doesn't say.
+fn os_username() -> PathBuf {
+    #[cfg(unix)]
+    let username = var_os("USER");
 
-            for (steam_pattern, steam_replacement) in replacements.iter().filter(|(p, _)| *p == "{SteamID64}" || *p == "{SteamID3}") {
-                let steam_str = steam_replacement.to_string_lossy();
-                let path_str = new_path.to_string_lossy();
-                if path_str.contains(steam_str.as_ref()) {
-                    return PathBuf::from(path_str.replace(steam_str.as_ref(), steam_pattern));
-                }
-            }
+    #[cfg(windows)]
+    let username = var_os("USERNAME");
 
-            return new_path;
-        }
-    }
+    username.or_else(|| home().file_name().map(ToOwned::to_owned)).map_or_else(|| PathBuf::from("*"), PathBuf::from)
+}
 
-    path.to_path_buf()
+/// `SteamID64` of a Steam account, or a wildcard matching any account when it isn't known.
+fn steam_id_64(steam_account_id: Option<&str>) -> PathBuf {
+    steam_account_id.map_or_else(|| PathBuf::from("*"), |id| PathBuf::from(SteamScanner::id3_to_id64(id.parse().unwrap()).to_string()))
 }
 
-#[cfg(all(unix, not(target_os = "macos")))]
-fn path_contains_subpath(haystack: &Path, needle: &str) -> bool {
-    haystack.ancestors().any(|ancestor| ancestor.ends_with(needle))
+/// Placeholders that can be used in game database paths.
+pub const PLACEHOLDERS: &[&str] = &[
+    "{AppData}",
+    "{Documents}",
+    "{GOGAppData}",
+    "{GameRoot}",
+    "{Home}",
+    "{LocalAppData}",
+    "{LocalLow}",
+    "{OSUserName}",
+    "{SavedGames}",
+    "{SteamID3}",
+    "{SteamID64}",
+    "{SteamUserData}",
+    "{StoreUserId}",
+    "{WinProgramData}",
+    "{WinPublic}",
+    "{XDGCache}",
+    "{XDGConfig}",
+    "{XDGData}",
+    "{XDGState}"
+];
+
+/// Every `{Name}` sequence in a path, whether or not it's a known placeholder. Braces around anything other than letters
+/// and digits, like GUIDs, are part of a file name.
+pub fn placeholders(path: &str) -> impl Iterator<Item = &str> {
+    path.match_indices('{')
+        .filter_map(|(start, _)| path[start..].find('}').map(|end| &path[start..=start + end]))
+        .filter(|placeholder| placeholder.len() > 2 && placeholder[1..placeholder.len() - 1].chars().all(char::is_alphanumeric))
 }
 
-#[cfg(all(unix, not(target_os = "macos")))]
-pub fn expand_path(path: &Path, installation_dir: Option<&Path>, prefix: Option<&Path>, steam_account_id: Option<&str>) -> PathBuf {
-    let mut replacements: Vec<(&str, PathBuf)> = vec![];
+/// Returns the first placeholder left in a path, meaning it couldn't be expanded without an installation directory or prefix.
+///
+/// Only whole components naming a known placeholder count, so directories like `{3F2504E0-4F89-11D3-9A0C-0305E82C3301}`
+/// are left alone.
+pub fn unresolved_placeholder(path: &Path) -> Option<String> {
+    path.components()
+        .map(Component::as_os_str)
+        .find(|component| PLACEHOLDERS.iter().any(|placeholder| *component == *placeholder))
+        .map(|placeholder| placeholder.to_string_lossy().into_owned())
+}
 
-    if let Some(install_dir) = installation_dir {
-        replacements.push(("{GameRoot}", install_dir.to_owned()));
-    }
+/// Splits a path inside a Wine prefix into its `drive_c` and the rest, which Windows programs treat as case-insensitive.
+#[cfg(unix)]
+fn split_drive_c<'a>(path: &'a Path, prefix: Option<&Path>) -> Option<(PathBuf, &'a Path)> {
+    let drive_c = prefix?.join("drive_c");
+    let rest = path.strip_prefix(&drive_c).ok()?;
 
-    let linux_app_data = app_data();
+    Some((drive_c, rest))
+}
 
-    if let Some(wine_prefix) = prefix {
-        let username = if path_contains_subpath(wine_prefix, "steamapps/compatdata") {
-            OsString::from("steamuser")
+/// Finds the files matching an expanded save path, ignoring case inside a Wine prefix as games there expect Windows
+/// semantics.
+#[cfg(unix)]
+pub fn glob_save_path(pattern: &Path, prefix: Option<&Path>) -> Result<Vec<Result<PathBuf, ReadError>>, PatternError> {
+    let case_insensitive_from = split_drive_c(pattern, prefix).map_or(usize::MAX, |(drive_c, _)| drive_c.components().count());
+    find_matches(pattern, case_insensitive_from)
+}
+
+/// Finds the files matching an expanded save path.
+#[cfg(windows)]
+pub fn glob_save_path(pattern: &Path) -> Result<Vec<Result<PathBuf, ReadError>>, PatternError> {
+    find_matches(pattern, usize::MAX)
+}
+
+/// Swaps components of a path inside a Wine prefix for existing files or directories that only differ in case, so
+/// restoring doesn't create a second copy with different casing.
+#[cfg(unix)]
+pub fn match_existing_case(path: &Path, prefix: Option<&Path>) -> PathBuf {
+    let Some((mut result, rest)) = split_drive_c(path, prefix) else {
+        return path.to_path_buf();
+    };
+
+    for component in rest.components() {
+        let name = component.as_os_str();
+        let existing = if result.join(name).exists() {
+            None
         } else {
-            var_os("USER").unwrap()
+            read_dir(&result)
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .map(|entry| entry.file_name())
+                .find(|entry| eq_ignore_case(entry, name))
         };
 
-        let drive_c = wine_prefix.join("drive_c");
-        let user = drive_c.join("users").join(username);
-        let windows_app_data = user.join("AppData");
-        let documents = user.join("Documents");
-
-        let steam_id_3 = steam_account_id.map_or_else(|| PathBuf::from("*"), PathBuf::from);
-        let steam_id_64 = steam_account_id
-            .map_or_else(|| PathBuf::from("*"), |id| PathBuf::from(SteamScanner::id3_to_id64(id.parse().unwrap()).to_string()));
-        let steam_user_data = steam_account_id
-            .map_or_else(|| linux_app_data.join("Steam/userdata/[0-9]*"), |id| linux_app_data.join("Steam/userdata").join(id));
-
-        replacements.extend([
-            ("{AppData}", windows_app_data.join("Roaming")),
-            ("{Documents}", documents),
-            ("{Home}", user),
-            ("{LocalAppData}", windows_app_data.join("Local")),
-            ("{LocalLow}", windows_app_data.join("LocalLow")),
-            ("{GOGAppData}", windows_app_data.join("Local").join("GOG.com/Galaxy/Applications")),
-            ("{SteamID3}", steam_id_3),
-            ("{SteamID64}", steam_id_64),
-            ("{SteamUserData}", steam_user_data)
-        ]);
-    } else {
-        replacements.push(("{Home}", home()));
+        result.push(existing.as_deref().unwrap_or(name));
     }
 
-    replacements.extend([("{XDGConfig}", config()), ("{XDGData}", linux_app_data)]);
-
-    expand_path_components(path, &replacements)
+    result
 }
 
-#[cfg(windows)]
-pub fn expand_path(path: &Path, installation_dir: Option<&Path>, steam_account_id: Option<&str>) -> PathBuf {
-    let mut replacements: Vec<(&str, PathBuf)> = vec![];
-
-    if let Some(install_dir) = installation_dir {
-        replacements.push(("{GameRoot}", install_dir.to_owned()));
-    }
+/// Placeholders that stand for a single path component rather than a directory, so they're replaced anywhere after the
+/// directory placeholder when shrinking.
+const COMPONENT_PLACEHOLDERS: &[&str] = &["{OSUserName}", "{SteamID3}", "{SteamID64}", "{StoreUserId}"];
 
-    let roaming_app_data = config();
-    let local_app_data = app_data();
-    let home_dir = home();
+/// Whether a resolved value is a glob pattern standing in for an unknown account, which can't be shrunk back.
+fn is_pattern(value: &Path) -> bool {
+    value.to_string_lossy().contains(['*', '?', '['])
+}
 
-    let steam_id_3 = steam_account_id.map_or_else(|| PathBuf::from("*"), PathBuf::from);
-    let steam_id_64 = steam_account_id
-        .map_or_else(|| PathBuf::from("*"), |id| PathBuf::from(SteamScanner::id3_to_id64(id.parse().unwrap()).to_string()));
-    let steam_user_data = {
-        let base_path = steamlocate::SteamDir::locate()
-            .map_or_else(|_| PathBuf::from("C:/Program Files (x86)/Steam"), |dir| dir.path().to_path_buf());
+fn game_root(installation_dir: Option<&Path>) -> Vec<(&'static str, PathBuf)> {
+    installation_dir.map(|install_dir| ("{GameRoot}", install_dir.to_owned())).into_iter().collect()
+}
 
-        let userdata_path = base_path.join("userdata");
-        steam_account_id.map_or_else(|| userdata_path.join("[0-9]*"), |id| userdata_path.join(id))
-    };
+/// `{StoreUserId}` is the `SteamID64` in games from Steam. Other stores have IDs of their own, so any user matches.
+fn store_user_id(steam_account_id: Option<&str>, steam_game: bool) -> (&'static str, PathBuf) {
+    (
+        "{StoreUserId}",
+        if steam_game {
+            steam_id_64(steam_account_id)
+        } else {
+            PathBuf::from("*")
+        }
+    )
+}
 
-    replacements.extend([
-        ("{AppData}", roaming_app_data),
-        ("{Documents}", home_dir.join("Documents")),
-        ("{Home}", home_dir),
-        ("{LocalAppData}", local_app_data.clone()),
-        ("{LocalLow}", local_app_data.parent().unwrap().join("LocalLow")),
-        ("{GOGAppData}", local_app_data.join("GOG.com/Galaxy/Applications")),
-        ("{SteamID3}", steam_id_3),
-        ("{SteamID64}", steam_id_64),
-        ("{SteamUserData}", steam_user_data)
-    ]);
+fn steam_placeholders(steam_dir: &Path, steam_account_id: Option<&str>) -> [(&'static str, PathBuf); 3] {
+    let userdata = steam_dir.join("userdata");
 
-    expand_path_components(path, &replacements)
+    [
+        ("{SteamID3}", steam_account_id.map_or_else(|| PathBuf::from("*"), PathBuf::from)),
+        ("{SteamID64}", steam_id_64(steam_account_id)),
+        ("{SteamUserData}", steam_account_id.map_or_else(|| userdata.join("[0-9]*"), |id| userdata.join(id)))
+    ]
 }
 
-#[cfg(target_os = "macos")]
-pub fn expand_path(path: &Path, installation_dir: Option<&Path>, prefix: Option<&Path>, steam_account_id: Option<&str>) -> PathBuf {
-    let mut replacements: Vec<(&str, PathBuf)> = vec![];
-
-    if let Some(install_dir) = installation_dir {
-        replacements.push(("{GameRoot}", install_dir.to_owned()));
+/// Users found in Wine prefixes, so a prefix is only inspected once for both backups and restores.
+#[cfg(unix)]
+static WINE_USERS: Mutex<BTreeMap<PathBuf, OsString>> = Mutex::new(BTreeMap::new());
+
+/// Finds the user profile in a Wine prefix, as Proton, Bottles and prefixes copied from other machines don't use the name
+/// of the current user.
+///
+/// Profiles named after the current user are preferred, followed by Proton's `steamuser` and then the most recently
+/// modified profile. Prefixes without any profiles yet fall back to the user Wine or Proton would create.
+#[cfg(unix)]
+fn wine_username(prefix: &Path) -> OsString {
+    if let Some(username) = WINE_USERS.lock().unwrap().get(prefix) {
+        return username.clone();
     }
 
-    let home_dir = home();
-    let application_support = home_dir.join("Library/Application Support"); // app_data is not used here as most games don't use the XDG spec on MacOS
-    let steam_id_3 = steam_account_id.map_or_else(|| PathBuf::from("*"), PathBuf::from);
-    let steam_id_64 = steam_account_id
-        .map_or_else(|| PathBuf::from("*"), |id| PathBuf::from(SteamScanner::id3_to_id64(id.parse().unwrap()).to_string()));
-    let steam_user_data = steam_account_id
-        .map_or_else(|| application_support.join("Steam/userdata/[0-9]*"), |id| application_support.join("Steam/userdata").join(id));
+    let current_user = os_username().into_os_string();
+    let mut profiles: Vec<(OsString, SystemTime)> = read_dir(prefix.join("drive_c/users"))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter(|entry| !eq_ignore_case(&entry.file_name(), OsStr::new("Public")))
+        .map(|entry| (entry.file_name(), entry.metadata().and_then(|metadata| metadata.modified()).unwrap_or(SystemTime::UNIX_EPOCH)))
+        .collect();
 
-    replacements.extend([("{SteamID3}", steam_id_3), ("{SteamID64}", steam_id_64), ("{SteamUserData}", steam_user_data)]);
+    profiles.sort_by_key(|(name, modified)| (*name != current_user, name != "steamuser", Reverse(*modified)));
 
-    if let Some(wine_prefix) = prefix {
-        let username = var_os("USER").unwrap();
-
-        let drive_c = wine_prefix.join("drive_c");
-        let user = drive_c.join("users").join(username);
-        let windows_app_data = user.join("AppData");
-        let documents = user.join("Documents");
-
-        replacements.extend([
-            ("{AppData}", windows_app_data.join("Roaming")),
-            ("{Documents}", documents),
-            ("{Home}", user),
-            ("{LocalAppData}", windows_app_data.join("Local")),
-            ("{LocalLow}", windows_app_data.join("LocalLow")),
-            ("{GOGAppData}", windows_app_data.join("Local").join("GOG.com/Galaxy/Applications"))
-        ]);
-    } else {
-        replacements.extend([
-            ("{AppData}", application_support.clone()),
-            ("{Documents}", home_dir.join("Documents")),
-            ("{Home}", home_dir),
-            ("{GOGAppData}", application_support.join("GOG.com/Galaxy/Applications"))
-        ]);
+    let Some((username, _)) = profiles.into_iter().next() else {
+        return if path_contains_subpath(prefix, "steamapps/compatdata") {
+            OsString::from("steamuser")
+        } else {
+            current_user
+        };
+    };
+
+    if username != current_user {
+        log::debug!("Using Wine user {} in {}", username.display(), prefix.display());
     }
 
-    expand_path_components(path, &replacements)
+    WINE_USERS.lock().unwrap().insert(prefix.to_path_buf(), username.clone());
+    username
 }
 
-#[cfg(all(unix, not(target_os = "macos")))]
-pub fn shrink_path(path: &Path, installation_dir: Option<&Path>, prefix: Option<&Path>, steam_account_id: Option<&str>) -> PathBuf {
-    let mut replacements: Vec<(&str, PathBuf)> = vec![];
-
-    if let Some(install_dir) = installation_dir {
-        replacements.push(("{GameRoot}", install_dir.to_owned()));
-    }
+/// Windows folders of the Wine user in a prefix.
+#[cfg(unix)]
+fn wine_placeholders(prefix: &Path) -> [(&'static str, PathBuf); 10] {
+    let drive_c = prefix.join("drive_c");
+    let username = wine_username(prefix);
+    let user = drive_c.join("users").join(&username);
+    let windows_app_data = user.join("AppData");
+
+    [
+        ("{AppData}", windows_app_data.join("Roaming")),
+        ("{Documents}", user.join("Documents")),
+        ("{SavedGames}", user.join("Saved Games")),
+        ("{Home}", user),
+        ("{LocalAppData}", windows_app_data.join("Local")),
+        ("{LocalLow}", windows_app_data.join("LocalLow")),
+        ("{GOGAppData}", windows_app_data.join("Local/GOG.com/Galaxy/Applications")),
+        ("{OSUserName}", PathBuf::from(username)),
+        ("{WinProgramData}", drive_c.join("ProgramData")),
+        ("{WinPublic}", drive_c.join("users/Public"))
+    ]
+}
 
+/// Every placeholder that can be resolved for a game and its value, shared by [`expand_path`] and [`shrink_path`].
+/// `steam_game` is whether the game is from Steam, which `{StoreUserId}` depends on.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn resolve(
+    installation_dir: Option<&Path>, prefix: Option<&Path>, steam_account_id: Option<&str>, steam_game: bool
+) -> Vec<(&'static str, PathBuf)> {
+    let mut placeholders = game_root(installation_dir);
     let linux_app_data = app_data();
 
     if let Some(wine_prefix) = prefix {
-        let username = if path_contains_subpath(wine_prefix, "steamapps/compatdata") {
-            OsString::from("steamuser")
-        } else {
-            var_os("USER").unwrap()
-        };
-
-        let drive_c = wine_prefix.join("drive_c");
-        let user = drive_c.join("users").join(username);
-        let windows_app_data = user.join("AppData");
-
-        let steam_id_3 = steam_account_id.map_or_else(|| PathBuf::from("*"), PathBuf::from);
-        let steam_id_64 = steam_account_id
-            .map_or_else(|| PathBuf::from("*"), |id| PathBuf::from(SteamScanner::id3_to_id64(id.parse().unwrap()).to_string()));
-        let steam_user_data = steam_account_id
-            .map_or_else(|| linux_app_data.join("Steam/userdata/[0-9]*"), |id| linux_app_data.join("Steam/userdata").join(id));
-
-        replacements.extend([
-            ("{LocalLow}", windows_app_data.join("LocalLow")),
-            ("{LocalAppData}", windows_app_data.join("Local")),
-            ("{AppData}", windows_app_data.join("Roaming")),
-            ("{Documents}", user.join("Documents")),
-            ("{Home}", user),
-            ("{GOGAppData}", windows_app_data.join("Local").join("GOG.com/Galaxy/Applications")),
-            ("{SteamID3}", steam_id_3),
-            ("{SteamID64}", steam_id_64),
-            ("{SteamUserData}", steam_user_data)
-        ]);
+        placeholders.extend(wine_placeholders(wine_prefix));
+        placeholders.extend(steam_placeholders(&linux_app_data.join("Steam"), steam_account_id));
     } else {
-        replacements.push(("{Home}", home()));
+        placeholders.extend([("{Home}", home()), ("{OSUserName}", os_username())]);
     }
 
-    replacements.extend([("{XDGConfig}", config()), ("{XDGData}", linux_app_data)]);
+    placeholders.extend([
+        ("{XDGCache}", xdg_cache()),
+        ("{XDGConfig}", config()),
+        ("{XDGData}", linux_app_data),
+        ("{XDGState}", xdg_state())
+    ]);
+    placeholders.push(store_user_id(steam_account_id, steam_game));
 
-    shrink_path_components(path, &replacements)
+    placeholders
 }
 
+/// Every placeholder that can be resolved for a game and its value, shared by [`expand_path`] and [`shrink_path`].
+/// `steam_game` is whether the game is from Steam, which `{StoreUserId}` depends on.
 #[cfg(windows)]
-pub fn shrink_path(path: &Path, installation_dir: Option<&Path>, steam_account_id: Option<&str>) -> PathBuf {
-    let mut replacements: Vec<(&str, PathBuf)> = vec![];
-
-    if let Some(install_dir) = installation_dir {
-        replacements.push(("{GameRoot}", install_dir.to_owned()));
-    }
-
-    let roaming_app_data = config();
+fn resolve(installation_dir: Option<&Path>, steam_account_id: Option<&str>, steam_game: bool) -> Vec<(&'static str, PathBuf)> {
+    let mut placeholders = game_root(installation_dir);
     let local_app_data = app_data();
     let home_dir = home();
+    let steam_dir =
+        steamlocate::SteamDir::locate().map_or_else(|_| PathBuf::from("C:/Program Files (x86)/Steam"), |dir| dir.path().to_path_buf());
 
-    let steam_id_3 = steam_account_id.map_or_else(|| PathBuf::from("*"), PathBuf::from);
-    let steam_id_64 = steam_account_id
-        .map_or_else(|| PathBuf::from("*"), |id| PathBuf::from(SteamScanner::id3_to_id64(id.parse().unwrap()).to_string()));
-    let steam_user_data = {
-        let base_path = steamlocate::SteamDir::locate()
-            .map_or_else(|_| PathBuf::from("C:/Program Files (x86)/Steam"), |dir| dir.path().to_path_buf());
-
-        let userdata_path = base_path.join("userdata");
-        steam_account_id.map_or_else(|| userdata_path.join("[0-9]*"), |id| userdata_path.join(id))
-    };
-
-    replacements.extend([
-        ("{LocalLow}", local_app_data.parent().unwrap().join("LocalLow")),
-        ("{LocalAppData}", local_app_data.clone()),
-        ("{AppData}", roaming_app_data),
+    placeholders.extend([
+        ("{AppData}", config()),
         ("{Documents}", home_dir.join("Documents")),
+        ("{SavedGames}", home_dir.join("Saved Games")),
         ("{Home}", home_dir),
+        ("{LocalAppData}", local_app_data.clone()),
+        ("{LocalLow}", local_app_data.parent().unwrap().join("LocalLow")),
         ("{GOGAppData}", local_app_data.join("GOG.com/Galaxy/Applications")),
-        ("{SteamID3}", steam_id_3),
-        ("{SteamID64}", steam_id_64),
-        ("{SteamUserData}", steam_user_data)
+        ("{OSUserName}", os_username()),
+        ("{WinProgramData}", program_data()),
+        ("{WinPublic}", public())
     ]);
+    placeholders.extend(steam_placeholders(&steam_dir, steam_account_id));
+    placeholders.push(store_user_id(steam_account_id, steam_game));
 
-    shrink_path_components(path, &replacements)
+    placeholders
 }
 
+/// Every placeholder that can be resolved for a game and its value, shared by [`expand_path`] and [`shrink_path`].
+/// `steam_game` is whether the game is from Steam, which `{StoreUserId}` depends on.
 #[cfg(target_os = "macos")]
-pub fn shrink_path(path: &Path, installation_dir: Option<&Path>, prefix: Option<&Path>, steam_account_id: Option<&str>) -> PathBuf {
-    let mut replacements: Vec<(&str, PathBuf)> = vec![];
-
-    if let Some(install_dir) = installation_dir {
-        replacements.push(("{GameRoot}", install_dir.to_owned()));
-    }
-
+fn resolve(
+    installation_dir: Option<&Path>, prefix: Option<&Path>, steam_account_id: Option<&str>, steam_game: bool
+) -> Vec<(&'static str, PathBuf)> {
+    let mut placeholders = game_root(installation_dir);
     let home_dir = home();
-    let application_support = home_dir.join("Library/Application Support");
-    let steam_id_3 = steam_account_id.map_or_else(|| PathBuf::from("*"), PathBuf::from);
-    let steam_id_64 = steam_account_id
-        .map_or_else(|| PathBuf::from("*"), |id| PathBuf::from(SteamScanner::id3_to_id64(id.parse().unwrap()).to_string()));
-    let steam_user_data = steam_account_id
-        .map_or_else(|| application_support.join("Steam/userdata/[0-9]*"), |id| application_support.join("Steam/userdata").join(id));
+    let application_support = home_dir.join("Library/Application Support"); // app_data is not used here as most games don't use the XDG spec on MacOS
 
-    replacements.extend([("{SteamID3}", steam_id_3), ("{SteamID64}", steam_id_64), ("{SteamUserData}", steam_user_data)]);
+    placeholders.extend(steam_placeholders(&application_support.join("Steam"), steam_account_id));
+    placeholders.push(store_user_id(steam_account_id, steam_game));
 
     if let Some(wine_prefix) = prefix {
-        let username = var_os("USER").unwrap();
-
-        let drive_c = wine_prefix.join("drive_c");
-        let user = drive_c.join("users").join(username);
-        let windows_app_data = user.join("AppData");
-
-        replacements.extend([
-            ("{LocalLow}", windows_app_data.join("LocalLow")),
-            ("{LocalAppData}", windows_app_data.join("Local")),
-            ("{AppData}", windows_app_data.join("Roaming")),
-            ("{Documents}", user.join("Documents")),
-            ("{Home}", user),
-            ("{GOGAppData}", windows_app_data.join("Local").join("GOG.com/Galaxy/Applications"))
-        ]);
+        placeholders.extend(wine_placeholders(wine_prefix));
     } else {
-        replacements.extend([
+        placeholders.extend([
             ("{AppData}", application_support.clone()),
             ("{Documents}", home_dir.join("Documents")),
             ("{Home}", home_dir),
-            ("{GOGAppData}", application_support.join("GOG.com/Galaxy/Applications"))
+            ("{GOGAppData}", application_support.join("GOG.com/Galaxy/Applications")),
+            ("{OSUserName}", os_username())
         ]);
     }
 
-    shrink_path_components(path, &replacements)
+    placeholders
+}
+
+/// Placeholders [`resolve`] gives a value on a platform without a Wine prefix, for checking paths written for other
+/// platforms than the one Aletheia is running on.
+pub fn available_placeholders(platform: &str) -> Option<&'static [&'static str]> {
+    match platform {
+        "linux" => {
+            Some(&["{GameRoot}", "{Home}", "{OSUserName}", "{StoreUserId}", "{XDGCache}", "{XDGConfig}", "{XDGData}", "{XDGState}"])
+        }
+        "mac" => Some(&[
+            "{AppData}",
+            "{Documents}",
+            "{GOGAppData}",
+            "{GameRoot}",
+            "{Home}",
+            "{OSUserName}",
+            "{SteamID3}",
+            "{SteamID64}",
+            "{SteamUserData}",
+            "{StoreUserId}"
+        ]),
+        "windows" => Some(&[
+            "{AppData}",
+            "{Documents}",
+            "{GOGAppData}",
+            "{GameRoot}",
+            "{Home}",
+            "{LocalAppData}",
+            "{LocalLow}",
+            "{OSUserName}",
+            "{SavedGames}",
+            "{SteamID3}",
+            "{SteamID64}",
+            "{SteamUserData}",
+            "{StoreUserId}",
+            "{WinProgramData}",
+            "{WinPublic}"
+        ]),
+        _ => None
+    }
+}
+
+fn expand_path_components(path: &Path, placeholders: &[(&str, PathBuf)]) -> PathBuf {
+    path.components()
+        .map(|component| {
+            placeholders
+                .iter()
+                .find(|(placeholder, _)| component.as_os_str() == *placeholder)
+                .map_or_else(|| component.as_os_str(), |(_, value)| value.as_os_str())
+        })
+        .collect()
+}
+
+/// Replaces the longest directory a path is inside with its placeholder, then any component that is a username or
+/// account ID, so that expanding the result gives back the same path.
+fn shrink_path_components(path: &Path, placeholders: &[(&str, PathBuf)]) -> PathBuf {
+    let (components, directories): (Vec<_>, Vec<_>) = placeholders
+        .iter()
+        .filter(|(_, value)| !is_pattern(value))
+        .partition(|(placeholder, _)| COMPONENT_PLACEHOLDERS.contains(placeholder));
+
+    let Some((placeholder, rest)) = directories
+        .iter()
+        .filter_map(|(placeholder, value)| Some((placeholder, value.components().count(), path.strip_prefix(value).ok()?)))
+        .min_by_key(|(_, depth, _)| Reverse(*depth))
+        .map(|(placeholder, _, rest)| (placeholder, rest))
+    else {
+        return path.to_path_buf();
+    };
+
+    let mut shrunk = PathBuf::from(placeholder);
+
+    for component in rest.components() {
+        let component = component.as_os_str();
+        let replacement = components.iter().find(|(_, value)| value.as_os_str() == component);
+        shrunk.push(replacement.map_or(component, |(placeholder, _)| OsStr::new(placeholder)));
+    }
+
+    shrunk
+}
+
+#[cfg(unix)]
+fn path_contains_subpath(haystack: &Path, needle: &str) -> bool {
+    haystack.ancestors().any(|ancestor| ancestor.ends_with(needle))
+}
+
+#[cfg(unix)]
+pub fn expand_path(
+    path: &Path, installation_dir: Option<&Path>, prefix: Option<&Path>, steam_account_id: Option<&str>, steam_game: bool
+) -> PathBuf {
+    expand_path_components(path, &resolve(installation_dir, prefix, steam_account_id, steam_game))
+}
+
+#[cfg(windows)]
+pub fn expand_path(path: &Path, installation_dir: Option<&Path>, steam_account_id: Option<&str>, steam_game: bool) -> PathBuf {
+    expand_path_components(path, &resolve(installation_dir, steam_account_id, steam_game))
+}
+
+#[cfg(unix)]
+pub fn shrink_path(
+    path: &Path, installation_dir: Option<&Path>, prefix: Option<&Path>, steam_account_id: Option<&str>, steam_game: bool
+) -> PathBuf {
+    shrink_path_components(path, &resolve(installation_dir, prefix, steam_account_id, steam_game))
+}
+
+#[cfg(windows)]
+pub fn shrink_path(path: &Path, installation_dir: Option<&Path>, steam_account_id: Option<&str>, steam_game: bool) -> PathBuf {
+    shrink_path_components(path, &resolve(installation_dir, steam_account_id, steam_game))
 }
 
 pub fn get_size(path: &Path) -> u64 {
@@ -393,7 +517,7 @@ mod tests {
     #[test]
     fn test_path_expansion() {
         #[cfg(unix)]
-        let username = var_os("USER").unwrap();
+        let username = os_username();
 
         let home_dir = home();
         let root_dir = home_dir.join("Games/Unit Test");
@@ -408,15 +532,15 @@ mod tests {
             let prefix = home_dir.join("Games/UnitTest");
 
             assert_eq!(
-                expand_path(save_file_1, None, Some(&prefix), Some(&gabe_steam_id3)),
+                expand_path(save_file_1, None, Some(&prefix), Some(gabe_steam_id3), false),
                 prefix
                     .join("drive_c/users")
-                    .join(username)
+                    .join(&username)
                     .join("AppData/LocalLow/AllianceArts/All in Abyss/SaveData")
                     .join(gabe_steam_id64)
                     .join("GameData/GameSaveData_0.sav")
             );
-            assert_eq!(expand_path(save_file_2, Some(&root_dir), None, None), root_dir.join("SAVEDATA/SonicDX01.snc"));
+            assert_eq!(expand_path(save_file_2, Some(&root_dir), None, None, false), root_dir.join("SAVEDATA/SonicDX01.snc"));
         }
 
         #[cfg(all(unix, not(target_os = "macos")))]
@@ -424,7 +548,23 @@ mod tests {
             let save_file_3 = Path::new("{XDGData}/Terraria/Players/UnitTest.plr");
             let xdg_data = app_data();
 
-            assert_eq!(expand_path(save_file_3, None, None, None), xdg_data.join("Terraria/Players/UnitTest.plr"));
+            assert_eq!(expand_path(save_file_3, None, None, None, false), xdg_data.join("Terraria/Players/UnitTest.plr"));
+
+            let save_file_4 = Path::new("{WinPublic}/Documents/{OSUserName}/{StoreUserId}/save.dat");
+            let prefix = home_dir.join("Games/UnitTest");
+
+            assert_eq!(
+                expand_path(save_file_4, None, Some(&prefix), Some(gabe_steam_id3), true),
+                prefix.join("drive_c/users/Public/Documents").join(&username).join(gabe_steam_id64).join("save.dat")
+            );
+            assert_eq!(
+                expand_path(save_file_4, None, Some(&prefix), Some(gabe_steam_id3), false),
+                prefix.join("drive_c/users/Public/Documents").join(&username).join("*/save.dat")
+            );
+
+            let save_file_5 = Path::new("{XDGState}/UnitTest/save.dat");
+
+            assert_eq!(expand_path(save_file_5, None, None, None, false), xdg_state().join("UnitTest/save.dat"));
         }
 
         #[cfg(target_os = "macos")]
@@ -432,7 +572,7 @@ mod tests {
             let save_file_3 = Path::new("{AppData}/Terraria/Players/UnitTest.plr");
             let application_support = home_dir.join("Library/Application Support");
 
-            assert_eq!(expand_path(save_file_3, None, None, None), application_support.join("Terraria/Players/UnitTest.plr"));
+            assert_eq!(expand_path(save_file_3, None, None, None, false), application_support.join("Terraria/Players/UnitTest.plr"));
         }
 
         #[cfg(windows)]
@@ -440,14 +580,14 @@ mod tests {
             let save_file_3 = Path::new("{Documents}/My Games/Terraria/Players/UnitTest.plr");
 
             assert_eq!(
-                expand_path(save_file_1, None, Some(&gabe_steam_id3)),
+                expand_path(save_file_1, None, Some(gabe_steam_id3), false),
                 home_dir
                     .join("AppData/LocalLow/AllianceArts/All in Abyss/SaveData")
                     .join(gabe_steam_id64)
                     .join("GameData/GameSaveData_0.sav")
             );
-            assert_eq!(expand_path(save_file_2, Some(&root_dir), None), root_dir.join("SAVEDATA/SonicDX01.snc"));
-            assert_eq!(expand_path(save_file_3, None, None), home_dir.join("Documents/My Games/Terraria/Players/UnitTest.plr"));
+            assert_eq!(expand_path(save_file_2, Some(&root_dir), None, false), root_dir.join("SAVEDATA/SonicDX01.snc"));
+            assert_eq!(expand_path(save_file_3, None, None, false), home_dir.join("Documents/My Games/Terraria/Players/UnitTest.plr"));
         }
     }
 
@@ -460,7 +600,14 @@ mod tests {
             let save_file_1 = home_dir.join(".local/share/Steam/steamapps/common/Sonic Adventure DX/SAVEDATA/SonicDX01.snc");
             let installation_dir = home_dir.join(".local/share/Steam/steamapps/common/Sonic Adventure DX");
 
-            assert_eq!(shrink_path(&save_file_1, Some(&installation_dir), None, None), Path::new("{GameRoot}/SAVEDATA/SonicDX01.snc"));
+            assert_eq!(
+                shrink_path(&save_file_1, Some(&installation_dir), None, None, false),
+                Path::new("{GameRoot}/SAVEDATA/SonicDX01.snc")
+            );
+
+            let save_file_2 = app_data().join("Terraria/Players/UnitTest.plr");
+
+            assert_eq!(shrink_path(&save_file_2, None, None, None, false), Path::new("{XDGData}/Terraria/Players/UnitTest.plr"));
         }
 
         #[cfg(target_os = "macos")]
@@ -468,7 +615,10 @@ mod tests {
             let save_file_1 = home_dir.join("Games/Sonic Adventure DX/SAVEDATA/SonicDX01.snc");
             let installation_dir = home_dir.join("Games/Sonic Adventure DX");
 
-            assert_eq!(shrink_path(&save_file_1, Some(&installation_dir), None, None), Path::new("{GameRoot}/SAVEDATA/SonicDX01.snc"));
+            assert_eq!(
+                shrink_path(&save_file_1, Some(&installation_dir), None, None, false),
+                Path::new("{GameRoot}/SAVEDATA/SonicDX01.snc")
+            );
         }
 
         #[cfg(windows)]
@@ -476,7 +626,190 @@ mod tests {
             let save_file_1 = home_dir.join("Games/Sonic Adventure DX/SAVEDATA/SonicDX01.snc");
             let installation_dir = home_dir.join("Games/Sonic Adventure DX");
 
-            assert_eq!(shrink_path(&save_file_1, Some(&installation_dir), None), Path::new("{GameRoot}/SAVEDATA/SonicDX01.snc"));
+            assert_eq!(
+                shrink_path(&save_file_1, Some(&installation_dir), None, false),
+                Path::new("{GameRoot}/SAVEDATA/SonicDX01.snc")
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wine_username() {
+        let prefix = std::env::temp_dir().join("aletheia-wine-username");
+        std::fs::create_dir_all(prefix.join("drive_c/users/Public")).unwrap();
+        std::fs::create_dir_all(prefix.join("drive_c/users/bottles/Documents")).unwrap();
+
+        assert_eq!(wine_username(&prefix), "bottles");
+        assert_eq!(
+            expand_path(Path::new("{Documents}"), None, Some(&prefix), None, false),
+            prefix.join("drive_c/users/bottles/Documents")
+        );
+
+        std::fs::remove_dir_all(&prefix).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_prefix_case_insensitivity() {
+        let prefix = std::env::temp_dir().join("aletheia-case-insensitivity");
+        let saves = prefix.join("drive_c/users/Public/Documents/My Games/Foo");
+        std::fs::create_dir_all(&saves).unwrap();
+        std::fs::write(saves.join("Save1.sav"), []).unwrap();
+
+        let pattern = prefix.join("drive_c/users/Public/documents/My games/foo/*.SAV");
+        let found: Vec<PathBuf> = glob_save_path(&pattern, Some(&prefix)).unwrap().into_iter().filter_map(Result::ok).collect();
+
+        assert_eq!(found, [saves.join("Save1.sav")]);
+        assert!(glob_save_path(&pattern, None).unwrap().is_empty());
+
+        let restored = prefix.join("drive_c/users/public/DOCUMENTS/my games/New/Save2.sav");
+        let existing = prefix.join("drive_c/users/Public/Documents/My Games/New/Save2.sav");
+
+        assert_eq!(match_existing_case(&restored, Some(&prefix)), existing);
+
+        let accented = prefix.join("drive_c/users/Public/Documents/Éditions Île");
+        std::fs::create_dir_all(&accented).unwrap();
+        std::fs::write(accented.join("Sauvegarde.sav"), []).unwrap();
+
+        let pattern = prefix.join("drive_c/users/Public/Documents/éditions île/*.SAV");
+        let found: Vec<PathBuf> = glob_save_path(&pattern, Some(&prefix)).unwrap().into_iter().filter_map(Result::ok).collect();
+
+        assert_eq!(found, [accented.join("Sauvegarde.sav")]);
+
+        let pattern = prefix.join("drive_c/users/Public/Documents/ÉDITIONS ÎLE/sauvegarde.sav");
+        let found: Vec<PathBuf> = glob_save_path(&pattern, Some(&prefix)).unwrap().into_iter().filter_map(Result::ok).collect();
+
+        assert_eq!(found, [accented.join("Sauvegarde.sav")]);
+        assert_eq!(
+            match_existing_case(&prefix.join("drive_c/users/public/documents/éditions île/New.sav"), Some(&prefix)),
+            accented.join("New.sav")
+        );
+
+        std::fs::remove_dir_all(&prefix).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_matches() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = std::env::temp_dir().join("aletheia-non-utf8");
+        let save = dir.join(OsStr::from_bytes(b"\x83Z\x81[\x83u.sav"));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&save, []).unwrap();
+
+        let found: Vec<PathBuf> = glob_save_path(&dir.join("*.sav"), None).unwrap().into_iter().filter_map(Result::ok).collect();
+        assert_eq!(found, [save]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Every placeholder's value, with the wildcards standing in for unknown accounts filled in.
+    fn concrete_values(placeholders: &[(&'static str, PathBuf)]) -> Vec<(&'static str, PathBuf)> {
+        placeholders
+            .iter()
+            .map(|(placeholder, value)| {
+                let value = value
+                    .components()
+                    .map(|component| {
+                        if is_pattern(Path::new(&component)) {
+                            OsStr::new("22202")
+                        } else {
+                            component.as_os_str()
+                        }
+                    })
+                    .collect();
+
+                (*placeholder, value)
+            })
+            .collect()
+    }
+
+    /// Checks that files below every placeholder expand back to themselves after shrinking, for games installed nowhere in
+    /// particular and inside each directory placeholder, so nested placeholders like `{GameRoot}` inside `{Documents}` or
+    /// `{SteamUserData}` inside `{XDGData}` are covered.
+    fn assert_round_trip(
+        resolve: impl Fn(Option<&Path>) -> Vec<(&'static str, PathBuf)>, expand: impl Fn(&Path, Option<&Path>) -> PathBuf,
+        shrink: impl Fn(&Path, Option<&Path>) -> PathBuf
+    ) {
+        let is_directory = |(placeholder, _): &(&str, PathBuf)| !COMPONENT_PLACEHOLDERS.contains(placeholder);
+        let installation_dirs =
+            concrete_values(&resolve(None)).into_iter().filter(is_directory).map(|(_, value)| value.join("Unit Test"));
+
+        for installation_dir in std::iter::once(None).chain(installation_dirs.map(Some)) {
+            let installation_dir = installation_dir.as_deref();
+            let (directories, components): (Vec<_>, Vec<_>) =
+                concrete_values(&resolve(installation_dir)).into_iter().partition(is_directory);
+
+            for (_, directory) in &directories {
+                let paths = std::iter::once(directory.join("save.dat"))
+                    .chain(components.iter().map(|(_, component)| directory.join("Saves").join(component).join("save.dat")));
+
+                for path in paths {
+                    let shrunk_path = shrink(&path, installation_dir);
+                    assert_eq!(
+                        expand(&shrunk_path, installation_dir),
+                        path,
+                        "{} didn't round-trip through {}",
+                        path.display(),
+                        shrunk_path.display()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_path_round_trip() {
+        let home_dir = home();
+
+        for steam_id in [None, Some("22202")] {
+            for steam_game in [false, true] {
+                #[cfg(unix)]
+                for prefix in
+                    [None, Some(home_dir.join("Games/UnitTest")), Some(home_dir.join(".steam/steamapps/compatdata/22202/pfx"))]
+                {
+                    let prefix = prefix.as_deref();
+
+                    assert_round_trip(
+                        |installation_dir| resolve(installation_dir, prefix, steam_id, steam_game),
+                        |path, installation_dir| expand_path(path, installation_dir, prefix, steam_id, steam_game),
+                        |path, installation_dir| shrink_path(path, installation_dir, prefix, steam_id, steam_game)
+                    );
+                }
+
+                #[cfg(windows)]
+                assert_round_trip(
+                    |installation_dir| resolve(installation_dir, steam_id, steam_game),
+                    |path, installation_dir| expand_path(path, installation_dir, steam_id, steam_game),
+                    |path, installation_dir| shrink_path(path, installation_dir, steam_id, steam_game)
+                );
+            }
         }
     }
+
+    #[test]
+    fn test_available_placeholders() {
+        #[cfg(all(unix, not(target_os = "macos")))]
+        let platform = "linux";
+
+        #[cfg(target_os = "macos")]
+        let platform = "mac";
+
+        #[cfg(windows)]
+        let platform = "windows";
+
+        #[cfg(unix)]
+        let resolved = resolve(Some(Path::new("/games/Unit Test")), None, Some("22202"), false);
+
+        #[cfg(windows)]
+        let resolved = resolve(Some(Path::new("C:/Games/Unit Test")), Some("22202"), false);
+
+        let mut resolved: Vec<&str> = resolved.into_iter().map(|(placeholder, _)| placeholder).collect();
+        resolved.sort_unstable();
+
+        assert_eq!(available_placeholders(platform), Some(&resolved[..]));
+        assert!(available_placeholders("dos").is_none());
+    }
 }
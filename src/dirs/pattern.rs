@@ -0,0 +1,117 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use glob::{MatchOptions, Pattern, PatternError};
+use std::ffi::{OsStr, OsString};
+use std::fs::read_dir;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Directory that couldn't be read while looking for files matching a pattern.
+pub struct ReadError {
+    pub error: io::Error,
+    pub path: PathBuf
+}
+
+/// Finds the files and directories matching an expanded save path.
+///
+/// Unlike `glob::glob`, the pattern is matched one component at a time against the names returned by the filesystem, so
+/// names that aren't valid UTF-8 are kept as they are. Components from `case_insensitive_from` onwards are compared
+/// ignoring case.
+pub fn find_matches(pattern: &Path, case_insensitive_from: usize) -> Result<Vec<Result<PathBuf, ReadError>>, PatternError> {
+    let mut candidates = vec![PathBuf::new()];
+    let mut errors = vec![];
+
+    for (index, component) in pattern.components().enumerate() {
+        let name = component.as_os_str();
+        let options = MatchOptions { case_sensitive: index < case_insensitive_from, ..MatchOptions::new() };
+
+        candidates = match name.to_str().filter(|name| name.contains(['*', '?', '['])) {
+            Some("**") => candidates.into_iter().flat_map(|dir| descendants(dir, &mut errors)).collect(),
+            Some(name) => {
+                // glob only ignores the case of ASCII letters, so both sides are lowercased instead
+                let case_sensitive = options.case_sensitive;
+                let pattern = Pattern::new(&if case_sensitive {
+                    name.to_owned()
+                } else {
+                    name.to_lowercase()
+                })?;
+                let matches = |entry: &OsString| {
+                    let entry = entry.to_string_lossy();
+                    pattern.matches_with(
+                        &if case_sensitive {
+                            entry
+                        } else {
+                            entry.to_lowercase().into()
+                        },
+                        options
+                    )
+                };
+
+                candidates
+                    .into_iter()
+                    .flat_map(|dir| {
+                        entries(&dir, &mut errors).into_iter().filter(&matches).map(move |entry| dir.join(entry)).collect::<Vec<_>>()
+                    })
+                    .collect()
+            }
+            None => candidates
+                .into_iter()
+                .filter_map(|dir| {
+                    let path = dir.join(name);
+                    if options.case_sensitive || path.symlink_metadata().is_ok() {
+                        return Some(path);
+                    }
+
+                    let existing = entries(&dir, &mut errors).into_iter().find(|entry| eq_ignore_case(entry, name))?;
+                    Some(dir.join(existing))
+                })
+                .collect()
+        };
+    }
+
+    Ok(errors.into_iter().map(Err).chain(candidates.into_iter().filter(|path| path.symlink_metadata().is_ok()).map(Ok)).collect())
+}
+
+/// Whether two names only differ in case. Names that aren't valid UTF-8 only ignore the case of ASCII letters.
+pub fn eq_ignore_case(a: &OsStr, b: &OsStr) -> bool {
+    match (a.to_str(), b.to_str()) {
+        (Some(a), Some(b)) => a.to_lowercase() == b.to_lowercase(),
+        _ => a.eq_ignore_ascii_case(b)
+    }
+}
+
+/// Names in a directory, recording why it couldn't be read unless it doesn't exist.
+fn entries(dir: &Path, errors: &mut Vec<ReadError>) -> Vec<OsString> {
+    match read_dir(dir) {
+        Ok(entries) => {
+            let mut names: Vec<OsString> = entries.filter_map(Result::ok).map(|entry| entry.file_name()).collect();
+            names.sort();
+            names
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound || e.kind() == io::ErrorKind::NotADirectory => vec![],
+        Err(error) => {
+            errors.push(ReadError { error, path: dir.to_path_buf() });
+            vec![]
+        }
+    }
+}
+
+/// A directory followed by every directory below it, as matched by `**`.
+fn descendants(dir: PathBuf, errors: &mut Vec<ReadError>) -> Vec<PathBuf> {
+    let mut found = vec![];
+    let mut pending = vec![dir];
+
+    while let Some(dir) = pending.pop() {
+        for entry in entries(&dir, errors) {
+            let path = dir.join(&entry);
+            if path.is_dir() {
+                pending.push(path);
+            }
+        }
+
+        found.push(dir);
+    }
+
+    found
+}
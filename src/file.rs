@@ -10,12 +10,12 @@ pub fn hash_bytes(bytes: &[u8]) -> String {
     blake3::hash(bytes).to_hex().to_string()
 }
 
-pub fn hash_file(file_path: &Path) -> String {
-    let file = File::open(file_path).unwrap();
+pub fn hash_file(file_path: &Path) -> std::io::Result<String> {
+    let file = File::open(file_path)?;
     let mut reader = BufReader::new(file);
     let mut hasher = Hasher::new();
 
-    copy(&mut reader, &mut hasher).unwrap();
+    copy(&mut reader, &mut hasher)?;
 
-    hasher.finalize().to_hex().to_string()
+    Ok(hasher.finalize().to_hex().to_string())
 }
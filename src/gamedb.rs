@@ -1,14 +1,28 @@
 // SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
+mod compiled;
+mod lint;
+mod ludusavi;
+mod matching;
+mod signature;
+
+pub use lint::{Severity, lint};
+pub use matching::NearMiss;
+
 use crate::config::Config;
-use crate::dirs::cache;
-use crate::scanner::{Game, Scanner};
+use crate::dirs::{cache, expand_path, glob_save_path, unresolved_placeholder};
+use crate::http::{self, HttpClient};
+use crate::scanner::{Game, Scanner, StoreId};
 use crate::scanner::{HeroicScanner, SteamScanner};
+use matching::Matcher;
 use reqwest::{StatusCode, header};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs::{File, create_dir_all, read_to_string, write};
+use std::fs::{File, copy, create_dir_all, read_dir, read_to_string, remove_file, rename, write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::UNIX_EPOCH;
 
 #[cfg(all(unix, not(target_os = "macos")))]
 use crate::scanner::LutrisScanner;
@@ -20,23 +34,161 @@ use crate::scanner::GOGScanner;
 use crate::scanner::XboxScanner;
 
 const GAMEDB_YAML: &str = include_str!("../resources/gamedb.yaml");
+/// Cache file naming the database the last update replaced, which is the only one rollback restores.
+const LAST_UPDATE: &str = "gamedb.last_update";
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error("{0} has an invalid signature: {1}")]
+    BadSignature(String, String),
+    #[error("{0} is version {1}, older than the installed version {2}")]
+    Downgrade(String, u64, u64),
+    #[error("{0} doesn't contain any games")]
+    Empty(String),
+    #[error(transparent)]
+    Http(#[from] http::Error),
+    #[error("{0} is invalid: {1}")]
+    Invalid(String, String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error)
+    Network(#[from] reqwest::Error),
+    #[error("No previous GameDB to roll back to")]
+    NoBackup,
+    #[error("This build of Aletheia has no GameDB public key, so GameDB updates can't be verified")]
+    NoPublicKey,
+    #[error("Failed to parse GameDB: {0}")]
+    Parse(#[from] serde_yaml::Error),
+    #[error("Download of {0} was cut short, expected {1} bytes but received {2}")]
+    Truncated(String, u64, usize),
+    #[error("{0} isn't signed")]
+    Unsigned(String)
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
 
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize)]
 pub struct GameDbEntry {
-    pub files: GameFiles
+    /// Other titles the game is sold under.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub files: GameFiles,
+    #[serde(default)]
+    pub ids: StoreIds,
+    /// How this entry is combined with an entry for the same game from a database merged before it.
+    #[serde(default, skip_serializing_if = "MergeModes::is_default")]
+    pub merge: MergeModes
 }
 
+impl GameDbEntry {
+    /// Combines an entry from a database merged later into this one, following the later entry's merge modes.
+    fn merge(&mut self, other: Self) {
+        match other.merge.aliases {
+            MergeMode::Append => {
+                for alias in other.aliases {
+                    if !self.aliases.contains(&alias) {
+                        self.aliases.push(alias);
+                    }
+                }
+            }
+            MergeMode::Replace => self.aliases = other.aliases
+        }
+
+        match other.merge.files {
+            MergeMode::Append => self.files.append(other.files),
+            MergeMode::Replace => self.files = other.files
+        }
+
+        match other.merge.ids {
+            MergeMode::Append => self.ids.append(other.ids),
+            MergeMode::Replace => self.ids = other.ids
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMode {
+    /// Adds to the field of the earlier entry, keeping what it already had.
+    Append,
+    /// Discards the field of the earlier entry.
+    #[default]
+    Replace
+}
+
+/// Merge mode of each field of an entry.
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MergeModes {
+    pub aliases: MergeMode,
+    pub files: MergeMode,
+    pub ids: MergeMode
+}
+
+impl MergeModes {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fields that are appended rather than replaced.
+    pub fn appended(&self) -> Vec<&'static str> {
+        [("aliases", self.aliases), ("files", self.files), ("ids", self.ids)]
+            .into_iter()
+            .filter_map(|(field, mode)| (mode == MergeMode::Append).then_some(field))
+            .collect()
+    }
+}
+
+/// Database that contributed to a merged entry.
 #[derive(Clone, Deserialize, Serialize)]
+pub struct Contribution {
+    pub source: String,
+    /// Merge modes of the contributed entry, or `None` for the database that first defined the game.
+    pub merge: Option<MergeModes>
+}
+
+/// Store identifiers used to match installed games to entries when their titles differ.
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct StoreIds {
+    pub epic: Option<String>,
+    pub gog: Option<u64>,
+    pub lutris: Option<String>,
+    pub steam: Option<u32>
+}
+
+impl StoreIds {
+    fn iter(&self) -> impl Iterator<Item = StoreId> {
+        [
+            self.epic.clone().map(StoreId::Epic),
+            self.gog.map(StoreId::Gog),
+            self.lutris.clone().map(StoreId::Lutris),
+            self.steam.map(StoreId::Steam)
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Whether the entry has an ID for the same store as `id` that doesn't match it, meaning it's a different game.
+    fn conflicts_with(&self, id: &StoreId) -> bool {
+        match id {
+            StoreId::Epic(epic) => self.epic.as_ref().is_some_and(|own| own != epic),
+            StoreId::Gog(gog) => self.gog.is_some_and(|own| own != *gog),
+            StoreId::Lutris(slug) => self.lutris.as_ref().is_some_and(|own| own != slug),
+            StoreId::Steam(app_id) => self.steam.is_some_and(|own| own != *app_id)
+        }
+    }
+
+    /// Takes the IDs set in `other`, keeping the existing ones for other stores.
+    fn append(&mut self, other: Self) {
+        self.epic = other.epic.or(self.epic.take());
+        self.gog = other.gog.or(self.gog);
+        self.lutris = other.lutris.or(self.lutris.take());
+        self.steam = other.steam.or(self.steam);
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize)]
 pub struct GameFiles {
     pub windows: Option<Vec<String>>,
     #[cfg(all(unix, not(target_os = "macos")))]
@@ -45,6 +197,48 @@ pub struct GameFiles {
     pub mac: Option<Vec<String>>
 }
 
+impl GameFiles {
+    /// Paths for the platform Aletheia is running on, excluding Windows paths that only exist inside a Wine prefix.
+    #[cfg(windows)]
+    pub const fn native(&self) -> Option<&Vec<String>> {
+        self.windows.as_ref()
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub const fn native(&self) -> Option<&Vec<String>> {
+        self.linux.as_ref()
+    }
+
+    #[cfg(target_os = "macos")]
+    pub const fn native(&self) -> Option<&Vec<String>> {
+        self.mac.as_ref()
+    }
+
+    fn append(&mut self, other: Self) {
+        append_paths(&mut self.windows, other.windows);
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        append_paths(&mut self.linux, other.linux);
+
+        #[cfg(target_os = "macos")]
+        append_paths(&mut self.mac, other.mac);
+    }
+}
+
+fn append_paths(paths: &mut Option<Vec<String>>, other: Option<Vec<String>>) {
+    let Some(other) = other else {
+        return;
+    };
+
+    let paths = paths.get_or_insert_with(Vec::new);
+
+    for path in other {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct Manifest {
     pub name: String,
@@ -62,6 +256,9 @@ pub struct FileMetadata {
 #[derive(Deserialize, Serialize)]
 struct CustomDbMetadata {
     etag: Option<String>,
+    /// Public key the database was verified with when it was cached.
+    #[serde(default)]
+    key: Option<String>,
     data: HashMap<String, GameDbEntry>
 }
 
@@ -70,24 +267,115 @@ struct CustomDbCache {
     databases: HashMap<String, CustomDbMetadata>
 }
 
-pub fn parse() -> HashMap<String, GameDbEntry> {
+/// Merged game database, along with the databases that contributed to each entry.
+#[derive(Default)]
+pub struct GameDb {
+    pub entries: HashMap<String, GameDbEntry>,
+    pub sources: HashMap<String, Vec<Contribution>>
+}
+
+impl GameDb {
+    /// Merges a database into this one, following the merge modes of its entries.
+    fn merge_layer(&mut self, source: &str, entries: HashMap<String, GameDbEntry>) {
+        // Sorted so the merge doesn't depend on the iteration order of the map
+        let mut entries: Vec<(String, GameDbEntry)> = entries.into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (name, entry) in entries {
+            let contributions = self.sources.entry(name.clone()).or_default();
+
+            if let Some(existing) = self.entries.get_mut(&name) {
+                log::debug!("{source} overrides {name}, appending {:?}", entry.merge.appended());
+                contributions.push(Contribution { source: source.to_owned(), merge: Some(entry.merge.clone()) });
+                existing.merge(entry);
+            } else {
+                contributions.push(Contribution { source: source.to_owned(), merge: None });
+                self.entries.insert(name, entry);
+            }
+        }
+    }
+}
+
+/// Database shared by the whole process, along with the config it was merged for.
+struct Shared {
+    custom_databases: Vec<String>,
+    custom_games: HashMap<String, GameDbEntry>,
+    db: Arc<GameDb>
+}
+
+static SHARED: RwLock<Option<Shared>> = RwLock::new(None);
+
+/// Returns the merged database: the official database, custom databases in the order they're configured and finally the
+/// config's own entries.
+///
+/// The official and custom databases are compiled into a binary cache on first use, and the result is kept in memory until
+/// the config or a cached database changes.
+pub fn load(config: &Config) -> Arc<GameDb> {
+    if let Some(shared) = SHARED.read().unwrap().as_ref()
+        && shared.custom_databases == config.custom_databases
+        && shared.custom_games == config.custom_games
+    {
+        return Arc::clone(&shared.db);
+    }
+
+    let fingerprint = compiled::fingerprint(GAMEDB_YAML, &config.custom_databases);
+    let mut db = compiled::read(&fingerprint).unwrap_or_else(|| {
+        let db = compile(&config.custom_databases);
+        compiled::write(&fingerprint, &db);
+        db
+    });
+
+    db.merge_layer("config", config.custom_games.clone());
+
+    let db = Arc::new(db);
+    *SHARED.write().unwrap() = Some(Shared {
+        custom_databases: config.custom_databases.clone(),
+        custom_games: config.custom_games.clone(),
+        db: Arc::clone(&db)
+    });
+
+    db
+}
+
+/// Drops the in-memory database so the next [`load`] picks up updated caches.
+fn invalidate() {
+    *SHARED.write().unwrap() = None;
+}
+
+/// Loads the cached official database, falling back to the version it replaced and then to the built-in one.
+fn load_official() -> HashMap<String, GameDbEntry> {
     let gamedb_path = cache().join("gamedb.yaml");
 
-    let mut db: HashMap<String, GameDbEntry> = if gamedb_path.exists() {
-        serde_yaml::from_reader(File::open(gamedb_path).unwrap()).unwrap_or_else(|_| {
-            log::error!("Failed to parse cached GameDB, falling back to built-in.");
-            serde_yaml::from_str(GAMEDB_YAML).expect("Failed to parse GameDB.")
-        })
-    } else {
-        serde_yaml::from_str(GAMEDB_YAML).expect("Failed to parse GameDB.")
-    };
+    for path in [gamedb_path.clone(), backup_path(&gamedb_path)] {
+        if !path.exists() {
+            continue;
+        }
+
+        match File::open(&path).map_err(Error::from).and_then(|file| Ok(serde_yaml::from_reader(file)?)) {
+            Ok(db) => return db,
+            Err(e) => log::error!("Failed to parse cached GameDB {}: {e}", path.display())
+        }
+    }
+
+    serde_yaml::from_str(GAMEDB_YAML).expect("Failed to parse GameDB.")
+}
+
+fn compile(custom_databases: &[String]) -> GameDb {
+    let mut custom_dbs = load_custom_db_cache().databases;
+    let mut db = GameDb::default();
+
+    db.merge_layer("official GameDB", load_official());
+
+    for source in custom_databases {
+        if let Some(custom_db) = custom_dbs.remove(source) {
+            db.merge_layer(source, custom_db.data);
+        }
+    }
 
-    db.extend(load_custom_db_cache().databases.into_values().flat_map(|custom_db| custom_db.data));
     db
 }
 
-pub fn get_installed_games() -> Vec<Game> {
-    let db = parse();
+fn scan_games() -> Vec<Game> {
     let mut games = vec![];
 
     #[cfg(all(unix, not(target_os = "macos")))]
@@ -103,66 +391,296 @@ pub fn get_installed_games() -> Vec<Game> {
     games.extend(SteamScanner::get_games());
 
     games
+}
+
+pub fn get_installed_games(config: &Config) -> Vec<Game> {
+    let db = load(config);
+    let matcher = Matcher::new(&db.entries);
+
+    scan_games()
         .into_iter()
         .filter_map(|mut game| {
-            if db.contains_key(&game.name) {
-                return Some(game);
-            }
-
-            let clean_name = game.name.replace("™", "").replace("®", "").trim().to_owned();
-            db.contains_key(&clean_name).then(|| {
-                game.name = clean_name;
-                game
-            })
+            let name = matcher.find(&game)?;
+            game.name.clone_from(name);
+            Some(game)
         })
         .collect()
 }
 
-pub fn update() -> Result<bool> {
+/// Lists installed games that didn't match any database entry but have a similar title to one.
+pub fn get_near_misses(config: &Config) -> Vec<NearMiss> {
+    let db = load(config);
+    let matcher = Matcher::new(&db.entries);
+    let mut near_misses: Vec<NearMiss> =
+        scan_games().iter().filter(|game| matcher.find(game).is_none()).filter_map(|game| matcher.near_miss(game)).collect();
+
+    near_misses.sort_by(|a, b| a.title.cmp(&b.title));
+    near_misses
+}
+
+/// Finds games that aren't installed anymore but still have saves in the user's home directory.
+///
+/// Only native paths that don't depend on `{GameRoot}` or a Wine prefix are considered.
+pub fn get_orphaned_games(config: &Config, installed_games: &[Game]) -> Vec<Game> {
+    let steam_account_id = config.steam_account_id.as_deref();
+    let mut games: Vec<Game> = load(config)
+        .entries
+        .iter()
+        .filter(|(name, _)| !installed_games.iter().any(|game| game.name == **name))
+        .filter(|(_, entry)| {
+            entry.files.native().is_some_and(|paths| paths.iter().any(|path| has_orphaned_files(path, steam_account_id)))
+        })
+        .map(|(name, _)| Game::uninstalled(name.clone()))
+        .collect();
+
+    games.sort_by(|a, b| a.name.cmp(&b.name));
+    games
+}
+
+fn has_orphaned_files(path: &str, steam_account_id: Option<&str>) -> bool {
+    if path.contains("{GameRoot}") {
+        return false;
+    }
+
+    #[cfg(unix)]
+    let expanded = expand_path(Path::new(path), None, None, steam_account_id, false);
+
+    #[cfg(windows)]
+    let expanded = expand_path(Path::new(path), None, steam_account_id, false);
+
+    if unresolved_placeholder(&expanded).is_some() {
+        return false;
+    }
+
+    #[cfg(unix)]
+    let found = glob_save_path(&expanded, None);
+
+    #[cfg(windows)]
+    let found = glob_save_path(&expanded);
+
+    found.is_ok_and(|paths| paths.into_iter().any(|found| found.is_ok_and(|found| found.is_file())))
+}
+
+pub fn update(config: &Config) -> Result<bool> {
     let cache_dir = cache();
 
     let gamedb_path = cache_dir.join("gamedb.yaml");
     let etag_path = cache_dir.join("gamedb.etag");
+    let version_path = cache_dir.join("gamedb.version");
 
+    // Without a key every download would be rejected, so don't download anything
+    signature::official_public_key()?;
     create_dir_all(cache_dir)?;
 
-    let previous_etag = etag_path.exists().then(|| read_to_string(&etag_path).ok()).flatten();
+    let previous_etag = gamedb_path.exists().then(|| read_to_string(&etag_path).ok()).flatten();
+    let client = HttpClient::new(&config.http)?;
+
+    // A mirror serving a database that fails verification is treated like one that can't be reached
+    let downloaded = client.with_mirrors(|base| {
+        let url = format!("{base}/gamedb.yaml");
+        let Some((contents, etag)) = download(&client, &url, previous_etag.as_deref())? else {
+            return Ok(None);
+        };
+
+        let version = signature::verify_official(&contents, || signature::fetch(&client, &url))?;
+        check_version(&url, version, &version_path)?;
+        validate_official(&contents)?;
+        Ok::<_, Error>(Some((contents, etag, version)))
+    })?;
 
-    let client = reqwest::blocking::Client::new();
-    let mut request = client
-        .get("https://raw.githubusercontent.com/Spencer-0003/aletheia/refs/heads/master/resources/gamedb.yaml")
-        .header(reqwest::header::USER_AGENT, concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")));
+    let Some((contents, etag, version)) = downloaded else {
+        return Ok(false);
+    };
+
+    replace_cache(&gamedb_path, &contents)?;
+    write_version(version, &version_path)?;
+
+    match etag {
+        Some(etag) => write(&etag_path, etag)?,
+        None if etag_path.exists() => remove_file(&etag_path)?,
+        None => {}
+    }
+
+    invalidate();
+    Ok(true)
+}
+
+/// Replaces the cached official database with a copy from disk, for machines that can't download it. The file needs a valid
+/// signature next to it, like a downloaded update.
+pub fn import(path: &Path) -> Result<()> {
+    let contents = std::fs::read(path)?;
+
+    let cache_dir = cache();
+    let version_path = cache_dir.join("gamedb.version");
+    let version = signature::verify_official(&contents, || signature::read(path))?;
+
+    check_version(&path.display().to_string(), version, &version_path)?;
+    validate_official(&contents)?;
+
+    create_dir_all(&cache_dir)?;
+    replace_cache(&cache_dir.join("gamedb.yaml"), &contents)?;
+    write_version(version, &version_path)?;
+
+    // The imported copy has no ETag, so forget the old one to download the latest version on the next update
+    remove_file(cache_dir.join("gamedb.etag")).ok();
+    invalidate();
+    Ok(())
+}
+
+/// Restores the database the last update replaced to the version cached before it.
+pub fn rollback() -> Result<()> {
+    let cache_dir = cache();
+    let record = cache_dir.join(LAST_UPDATE);
+
+    let name = read_to_string(&record).unwrap_or_default();
+    let path = match name.trim() {
+        name @ ("gamedb.yaml" | "custom_gamedb.yaml") => cache_dir.join(name),
+        _ => return Err(Error::NoBackup)
+    };
+
+    let backup = backup_path(&path);
+    if !backup.exists() {
+        return Err(Error::NoBackup);
+    }
+
+    rename(backup, &path)?;
+    remove_file(record)?;
+
+    // The version and ETag belong to the version that was rolled back, so forget them to download the latest version again
+    if name.trim() == "gamedb.yaml" {
+        let version_path = cache_dir.join("gamedb.version");
+        let version_backup = backup_path(&version_path);
+
+        if version_backup.exists() {
+            rename(version_backup, version_path)?;
+        } else if version_path.exists() {
+            remove_file(version_path)?;
+        }
+
+        remove_file(cache_dir.join("gamedb.etag")).ok();
+    }
+
+    invalidate();
+    Ok(())
+}
+
+/// Checks a downloaded official database parses and lints without errors before it replaces the cached one.
+fn validate_official(contents: &[u8]) -> Result<()> {
+    let source = std::str::from_utf8(contents).map_err(|e| Error::Invalid("GameDB".to_owned(), e.to_string()))?;
+    let db: HashMap<String, GameDbEntry> =
+        serde_yaml::from_str(source).map_err(|e| Error::Invalid("GameDB".to_owned(), e.to_string()))?;
+
+    if db.is_empty() {
+        return Err(Error::Empty("GameDB".to_owned()));
+    }
+
+    if let Some(problem) = lint(source).into_iter().find(|problem| problem.severity == Severity::Error) {
+        return Err(Error::Invalid("GameDB".to_owned(), format!("line {}: {}", problem.line, problem.message)));
+    }
+
+    Ok(())
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Rejects an official database signed with an older version than the newest one accepted before, so an old copy with a
+/// valid signature can't be replayed to bring back outdated save paths.
+fn check_version(source: &str, version: u64, version_path: &Path) -> Result<()> {
+    let installed = read_to_string(version_path).ok().and_then(|installed| installed.trim().parse::<u64>().ok());
+
+    match installed {
+        Some(installed) if version < installed => Err(Error::Downgrade(source.to_owned(), version, installed)),
+        _ => Ok(())
+    }
+}
+
+/// Records the version of a new official database, keeping the one it replaces next to it for rollback.
+fn write_version(version: u64, version_path: &Path) -> Result<()> {
+    let backup = backup_path(version_path);
+
+    if version_path.exists() {
+        copy(version_path, &backup)?;
+    } else if backup.exists() {
+        remove_file(&backup)?;
+    }
+
+    write(version_path, version.to_string())?;
+    Ok(())
+}
+
+/// Writes a cache file through a temporary file, keeping the version it replaces next to it for rollback.
+fn replace_cache(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+
+    write(&temp_path, contents)?;
+
+    // A backup left from before the cache went missing isn't the version being replaced
+    let backup = backup_path(path);
+    if path.exists() {
+        copy(path, &backup)?;
+    } else if backup.exists() {
+        remove_file(&backup)?;
+    }
+
+    rename(&temp_path, path)?;
+
+    let record = path.with_file_name(LAST_UPDATE);
+    match path.file_name() {
+        Some(name) if backup.exists() => write(record, name.as_encoded_bytes())?,
+        _ if record.exists() => remove_file(record)?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Downloads a file unless it still matches `etag`, returning its contents and new `ETag`.
+fn download(client: &HttpClient, url: &str, etag: Option<&str>) -> Result<Option<(Vec<u8>, Option<String>)>> {
+    let mut request = client.get(url);
 
-    if let Some(ref etag) = previous_etag {
+    if let Some(etag) = etag {
         request = request.header(header::IF_NONE_MATCH, etag);
     }
 
     let response = request.send()?.error_for_status()?;
-    let status = response.status();
 
-    if status == StatusCode::NOT_MODIFIED {
-        return Ok(false);
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(None);
     }
 
-    let current_etag = response.headers().get(header::ETAG).unwrap();
+    let etag = response.headers().get(header::ETAG).and_then(|etag| etag.to_str().ok()).map(ToOwned::to_owned);
+    let expected_length = response.content_length();
+    let contents = response.bytes()?.to_vec();
 
-    write(&etag_path, current_etag.as_bytes())?;
-    write(&gamedb_path, response.bytes()?)?;
+    if let Some(expected_length) = expected_length
+        && expected_length != contents.len() as u64
+    {
+        return Err(Error::Truncated(url.to_owned(), expected_length, contents.len()));
+    }
 
-    Ok(true)
+    Ok(Some((contents, etag)))
 }
 
 fn load_custom_db_cache() -> CustomDbCache {
     let cache_path = cache().join("custom_gamedb.yaml");
 
-    if cache_path.exists() {
-        serde_yaml::from_reader(File::open(&cache_path).unwrap()).unwrap_or_else(|_| {
-            log::error!("Failed to parse custom GameDB cache.");
-            CustomDbCache::default()
-        })
-    } else {
-        CustomDbCache::default()
+    for path in [cache_path.clone(), backup_path(&cache_path)] {
+        if !path.exists() {
+            continue;
+        }
+
+        match File::open(&path).map_err(Error::from).and_then(|file| Ok(serde_yaml::from_reader(file)?)) {
+            Ok(db_cache) => return db_cache,
+            Err(e) => log::error!("Failed to parse custom GameDB cache {}: {e}", path.display())
+        }
     }
+
+    CustomDbCache::default()
 }
 
 pub fn update_custom(cfg: &Config) -> Result<bool> {
@@ -172,36 +690,152 @@ pub fn update_custom(cfg: &Config) -> Result<bool> {
 
     let cache_dir = cache();
 
-    let client = reqwest::blocking::Client::new();
+    let client = HttpClient::new(&cfg.http)?;
     let mut db_cache = load_custom_db_cache();
     let mut updated = false;
 
     create_dir_all(&cache_dir)?;
 
     for db in &cfg.custom_databases {
-        let mut request = client.get(db).header(header::USER_AGENT, concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")));
-        let cached_etag = db_cache.databases.get(db).and_then(|meta| meta.etag.as_ref());
-
-        if let Some(etag) = cached_etag {
-            request = request.header(header::IF_NONE_MATCH, etag);
-        }
+        let key = cfg.custom_database_keys.get(db).map(String::as_str);
 
-        let response = request.send()?.error_for_status()?;
+        // Databases cached before their key was pinned have to be fetched and verified again
+        let cached_etag = db_cache.databases.get(db).filter(|meta| meta.key.as_deref() == key).and_then(|meta| meta.etag.as_deref());
+        let fetched = if is_remote(db) {
+            fetch_custom_db(&client, db, cached_etag, key)?
+        } else {
+            read_custom_db(Path::new(db.strip_prefix("file://").unwrap_or(db)), cached_etag, key)?
+        };
 
-        if response.status() == StatusCode::NOT_MODIFIED {
+        let Some(fetched) = fetched else {
             continue;
-        }
-
-        let etag = response.headers().get(header::ETAG).and_then(|etag| etag.to_str().ok()).map(ToOwned::to_owned);
+        };
 
-        db_cache.databases.insert(db.clone(), CustomDbMetadata { etag, data: serde_yaml::from_reader(response).unwrap() });
+        db_cache.databases.insert(db.clone(), fetched);
         updated = true;
     }
 
     if updated {
-        db_cache.databases.retain(|url, _| cfg.custom_databases.contains(url));
-        serde_yaml::to_writer(File::create(cache_dir.join("custom_gamedb.yaml"))?, &db_cache).unwrap();
+        db_cache.databases.retain(|source, _| cfg.custom_databases.contains(source));
+        replace_cache(&cache_dir.join("custom_gamedb.yaml"), serde_yaml::to_string(&db_cache)?.as_bytes())?;
+        invalidate();
     }
 
     Ok(updated)
 }
+
+fn is_remote(db: &str) -> bool {
+    db.starts_with("http://") || db.starts_with("https://")
+}
+
+/// Reads databases to lint along with where they came from. Without `paths`, the built-in database and every local custom
+/// database are read.
+pub fn lint_sources(config: &Config, paths: &[String]) -> Result<Vec<(String, String)>> {
+    let mut sources = vec![];
+
+    let local: Vec<&String> = if paths.is_empty() {
+        sources.push(("resources/gamedb.yaml".to_owned(), GAMEDB_YAML.to_owned()));
+        config.custom_databases.iter().filter(|db| !is_remote(db)).collect()
+    } else {
+        paths.iter().collect()
+    };
+
+    for db in local {
+        for file in database_files(Path::new(db.strip_prefix("file://").unwrap_or(db)))? {
+            sources.push((file.display().to_string(), read_to_string(&file)?));
+        }
+    }
+
+    Ok(sources)
+}
+
+/// Downloads a custom database, returning `None` if it hasn't changed since it was cached. Databases with a pinned `key`
+/// must have a valid signature.
+fn fetch_custom_db(client: &HttpClient, url: &str, cached_etag: Option<&str>, key: Option<&str>) -> Result<Option<CustomDbMetadata>> {
+    let Some((contents, etag)) = download(client, url, cached_etag)? else {
+        return Ok(None);
+    };
+
+    if let Some(key) = key {
+        signature::verify(url, &contents, &signature::fetch(client, url)?, key)?;
+    }
+
+    let data = parse_custom_db(url, &contents)?;
+
+    if data.is_empty() {
+        return Err(Error::Empty(url.to_owned()));
+    }
+
+    Ok(Some(CustomDbMetadata { etag, key: key.map(ToOwned::to_owned), data }))
+}
+
+/// Files making up a local database, which is either a single file or a directory of YAML files.
+fn database_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files: Vec<PathBuf> = read_dir(path)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| file.is_file() && file.extension().is_some_and(|extension| extension == "yaml" || extension == "yml"))
+        .collect();
+
+    files.sort();
+    Ok(files)
+}
+
+/// Reads a custom database from a file or a directory of YAML files, using modification times in place of an `ETag`. With
+/// a pinned `key`, every file must have a valid signature next to it.
+fn read_custom_db(path: &Path, cached_etag: Option<&str>, key: Option<&str>) -> Result<Option<CustomDbMetadata>> {
+    let files = database_files(path)?;
+    let etag = files
+        .iter()
+        .map(|file| {
+            let modified = file.metadata()?.modified()?.duration_since(UNIX_EPOCH).unwrap_or_default();
+            Ok(format!("{}@{}", file.display(), modified.as_nanos()))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join(";");
+
+    if cached_etag == Some(etag.as_str()) {
+        return Ok(None);
+    }
+
+    let mut data = HashMap::new();
+    for file in &files {
+        let source = file.display().to_string();
+        let contents = std::fs::read(file)?;
+
+        if let Some(key) = key {
+            signature::verify(&source, &contents, &signature::read(file)?, key)?;
+        }
+
+        data.extend(parse_custom_db(&source, &contents)?);
+    }
+
+    if data.is_empty() {
+        return Err(Error::Empty(path.display().to_string()));
+    }
+
+    Ok(Some(CustomDbMetadata { etag: Some(etag), key: key.map(ToOwned::to_owned), data }))
+}
+
+/// Parses a custom database in either the Aletheia or the Ludusavi manifest format.
+fn parse_custom_db(source: &str, contents: &[u8]) -> Result<HashMap<String, GameDbEntry>> {
+    let invalid = |e: serde_yaml::Error| Error::Invalid(source.to_owned(), e.to_string());
+    let document: serde_yaml::Value = serde_yaml::from_slice(contents).map_err(invalid)?;
+
+    if ludusavi::is_manifest(&document) {
+        let conversion = ludusavi::convert(document).map_err(invalid)?;
+        if conversion.dropped > 0 {
+            log::warn!(
+                "Dropped {} save paths from {source} that use Ludusavi placeholders Aletheia doesn't support",
+                conversion.dropped
+            );
+        }
+
+        Ok(conversion.entries)
+    } else {
+        serde_yaml::from_value(document).map_err(invalid)
+    }
+}
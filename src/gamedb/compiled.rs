@@ -0,0 +1,104 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use super::{Contribution, GameDb, GameDbEntry, GameFiles, StoreIds};
+use crate::dirs::cache;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Bumped whenever the layout of the compiled database changes, so caches written by other versions are recompiled.
+const VERSION: u8 = 1;
+
+/// Cached files the official and custom databases are compiled from.
+const SOURCES: &[&str] = &["gamedb.yaml", "gamedb.yaml.bak", "gamedb.etag", "custom_gamedb.yaml", "custom_gamedb.yaml.bak"];
+
+/// Merged entry without its merge modes, which postcard can't store as they're skipped when serialised.
+#[derive(Deserialize, Serialize)]
+struct CompiledEntry {
+    aliases: Vec<String>,
+    files: GameFiles,
+    ids: StoreIds
+}
+
+impl From<&GameDbEntry> for CompiledEntry {
+    fn from(entry: &GameDbEntry) -> Self {
+        Self { aliases: entry.aliases.clone(), files: entry.files.clone(), ids: entry.ids.clone() }
+    }
+}
+
+impl From<CompiledEntry> for GameDbEntry {
+    fn from(entry: CompiledEntry) -> Self {
+        Self { aliases: entry.aliases, files: entry.files, ids: entry.ids, ..Self::default() }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct CompiledDb {
+    fingerprint: String,
+    entries: Vec<(String, CompiledEntry)>,
+    sources: Vec<(String, Vec<Contribution>)>
+}
+
+/// Hashes everything the compiled database depends on: the built-in database, the cached databases and their `ETag`s, and
+/// the order of the custom databases.
+pub fn fingerprint(builtin: &str, custom_databases: &[String]) -> String {
+    let cache_dir = cache();
+    let mut hasher = blake3::Hasher::new();
+
+    hasher.update(&[VERSION]);
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.update(blake3::hash(builtin.as_bytes()).as_bytes());
+
+    for source in SOURCES {
+        hasher.update(blake3::hash(&fs::read(cache_dir.join(source)).unwrap_or_default()).as_bytes());
+    }
+
+    for db in custom_databases {
+        hasher.update(blake3::hash(db.as_bytes()).as_bytes());
+    }
+
+    hasher.finalize().to_hex().to_string()
+}
+
+/// Loads the compiled database if it was compiled from the same sources.
+pub fn read(fingerprint: &str) -> Option<GameDb> {
+    let bytes = fs::read(cache().join("gamedb.bin")).ok()?;
+    let (&version, bytes) = bytes.split_first()?;
+
+    if version != VERSION {
+        return None;
+    }
+
+    let compiled: CompiledDb = postcard::from_bytes(bytes).map_err(|e| log::warn!("Failed to read compiled GameDB: {e}")).ok()?;
+
+    if compiled.fingerprint != fingerprint {
+        return None;
+    }
+
+    Some(GameDb {
+        entries: compiled.entries.into_iter().map(|(name, entry)| (name, entry.into())).collect(),
+        sources: compiled.sources.into_iter().collect()
+    })
+}
+
+/// Writes the compiled database, logging failures as the database can always be compiled again.
+pub fn write(fingerprint: &str, db: &GameDb) {
+    let compiled = CompiledDb {
+        fingerprint: fingerprint.to_owned(),
+        entries: db.entries.iter().map(|(name, entry)| (name.clone(), entry.into())).collect(),
+        sources: db.sources.clone().into_iter().collect()
+    };
+
+    let cache_dir = cache();
+    let temp_path = cache_dir.join("gamedb.bin.tmp");
+    let result = postcard::to_allocvec(&compiled).map_err(|e| e.to_string()).and_then(|bytes| {
+        fs::create_dir_all(&cache_dir)
+            .and_then(|()| fs::write(&temp_path, [&[VERSION], bytes.as_slice()].concat()))
+            .and_then(|()| fs::rename(&temp_path, cache_dir.join("gamedb.bin")))
+            .map_err(|e| e.to_string())
+    });
+
+    if let Err(e) = result {
+        log::warn!("Failed to write compiled GameDB: {e}");
+    }
+}
@@ -0,0 +1,277 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use super::{MergeModes, StoreIds, ludusavi};
+use crate::dirs::{PLACEHOLDERS, available_placeholders, placeholders};
+use crate::scanner::StoreId;
+use serde::Deserialize;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use std::collections::hash_map::Entry as MapEntry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning
+}
+
+/// Problem found in a game database, on a 1-based line.
+pub struct Problem {
+    pub line: usize,
+    pub message: String,
+    pub severity: Severity
+}
+
+impl Problem {
+    fn error(line: usize, message: String) -> Self {
+        Self { line, message, severity: Severity::Error }
+    }
+
+    fn warning(line: usize, message: String) -> Self {
+        Self { line, message, severity: Severity::Warning }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    #[serde(default)]
+    aliases: Vec<String>,
+    files: BTreeMap<String, Option<Vec<String>>>,
+    #[serde(default)]
+    ids: StoreIds,
+    #[serde(default)]
+    #[expect(dead_code, reason = "Only parsed to check the merge modes are valid")]
+    merge: MergeModes
+}
+
+/// Top-level entries in document order, keeping the duplicates a `HashMap` would silently drop.
+struct Entries(Vec<(String, serde_yaml::Value)>);
+
+impl<'de> Deserialize<'de> for Entries {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = Entries;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of game titles to entries")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Entries, A::Error> {
+                let mut entries = vec![];
+
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+/// Checks a database in the Aletheia format for unknown placeholders, invalid glob patterns, unknown platforms, duplicates
+/// and patterns that can only match directories.
+///
+/// Ludusavi manifests are only checked for paths that will be dropped when they're converted, as Aletheia can't use them.
+pub fn lint(source: &str) -> Vec<Problem> {
+    let entries = match serde_yaml::from_str::<Entries>(source) {
+        Ok(entries) => entries.0,
+        Err(e) => return vec![Problem::error(e.location().map_or(1, |location| location.line()), e.to_string())]
+    };
+
+    let document = serde_yaml::Value::Mapping(entries.iter().map(|(name, entry)| (name.as_str().into(), entry.clone())).collect());
+    if ludusavi::is_manifest(&document) {
+        return match ludusavi::convert(document) {
+            Ok(conversion) if conversion.dropped > 0 => {
+                vec![Problem::warning(
+                    1,
+                    format!("{} save paths use Ludusavi placeholders Aletheia doesn't support", conversion.dropped)
+                )]
+            }
+            Ok(_) => vec![],
+            Err(e) => vec![Problem::error(e.location().map_or(1, |location| location.line()), e.to_string())]
+        };
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let key_lines = key_lines(&lines);
+    let names: HashSet<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+    let mut problems = vec![];
+    let mut first_lines: HashMap<&str, usize> = HashMap::new();
+    let mut ids: HashMap<StoreId, &str> = HashMap::new();
+    let mut cursor = 0;
+
+    for (name, value) in &entries {
+        if let Some(offset) = key_lines[cursor..].iter().position(|(_, key)| key == name) {
+            cursor += offset + 1;
+        }
+
+        let line = cursor.checked_sub(1).map_or(1, |index| key_lines[index].0);
+        let end = key_lines.get(cursor).map_or(lines.len(), |(next, _)| next - 1);
+        let path_line =
+            |path: &str| lines[line - 1..end].iter().position(|text| text.contains(path)).map_or(line, |offset| line + offset);
+
+        match first_lines.entry(name) {
+            MapEntry::Occupied(first) => {
+                problems.push(Problem::error(line, format!("{name}: duplicate entry, first defined on line {}", first.get())));
+                continue;
+            }
+            MapEntry::Vacant(vacant) => {
+                vacant.insert(line);
+            }
+        }
+
+        let entry: Entry = match serde_yaml::from_value(value.clone()) {
+            Ok(entry) => entry,
+            Err(e) => {
+                problems.push(Problem::error(line, format!("{name}: {e}")));
+                continue;
+            }
+        };
+
+        for alias in &entry.aliases {
+            if alias != name && names.contains(alias.as_str()) {
+                problems.push(Problem::warning(path_line(alias), format!("{name}: alias {alias} is also the title of another entry")));
+            }
+        }
+
+        for id in entry.ids.iter() {
+            if let Some(other) = ids.get(&id) {
+                problems.push(Problem::error(line, format!("{name}: {} is also used by {other}", describe_id(&id))));
+            } else {
+                ids.insert(id, name);
+            }
+        }
+
+        if entry.files.values().all(|paths| paths.as_ref().is_none_or(Vec::is_empty)) {
+            problems.push(Problem::warning(line, format!("{name}: no save paths")));
+        }
+
+        for (platform, paths) in &entry.files {
+            let Some(available) = available_placeholders(platform) else {
+                problems.push(Problem::error(line, format!("{name}: unknown platform {platform}, expected linux, mac or windows")));
+                continue;
+            };
+
+            let paths = paths.as_deref().unwrap_or_default();
+
+            for (index, path) in paths.iter().enumerate() {
+                let line = path_line(path);
+
+                if paths[..index].contains(path) {
+                    problems.push(Problem::warning(line, format!("{name}: {path} is listed twice for {platform}")));
+                    continue;
+                }
+
+                problems.extend(check_path(path, platform, available).into_iter().map(|mut problem| {
+                    problem.line = line;
+                    problem.message = format!("{name}: {}", problem.message);
+                    problem
+                }));
+            }
+        }
+    }
+
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+fn check_path(path: &str, platform: &str, available: &[&str]) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    for placeholder in placeholders(path) {
+        if !PLACEHOLDERS.contains(&placeholder) {
+            let message = PLACEHOLDERS.iter().find(|known| known.eq_ignore_ascii_case(placeholder)).map_or_else(
+                || format!("unknown placeholder {placeholder}"),
+                |known| format!("unknown placeholder {placeholder}, did you mean {known}?")
+            );
+
+            problems.push(Problem::error(0, message));
+        } else if !Path::new(path).components().any(|component| component.as_os_str() == placeholder) {
+            problems.push(Problem::error(0, format!("{placeholder} must be a whole path component")));
+        } else if !available.contains(&placeholder) {
+            problems.push(Problem::warning(0, format!("{placeholder} can't be resolved in {platform} paths")));
+        }
+    }
+
+    if let Err(e) = glob::Pattern::new(path) {
+        problems.push(Problem::error(0, format!("invalid glob pattern {path}: {e}")));
+    }
+
+    if path.ends_with(['/', '\\']) || path.ends_with("**") {
+        problems.push(Problem::warning(0, format!("{path} only matches directories, save paths must match files")));
+    }
+
+    problems
+}
+
+/// Line numbers of top-level keys, found by parsing every unindented line on its own.
+fn key_lines(lines: &[&str]) -> Vec<(usize, String)> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with([' ', '\t', '#', '-']))
+        .filter_map(|(index, line)| {
+            let mapping: serde_yaml::Mapping = serde_yaml::from_str(line).ok()?;
+            Some((index + 1, mapping.keys().next()?.as_str()?.to_owned()))
+        })
+        .collect()
+}
+
+fn describe_id(id: &StoreId) -> String {
+    match id {
+        StoreId::Epic(app_name) => format!("Epic app name {app_name}"),
+        StoreId::Gog(product_id) => format!("GOG product ID {product_id}"),
+        StoreId::Lutris(slug) => format!("Lutris slug {slug}"),
+        StoreId::Steam(app_id) => format!("Steam app ID {app_id}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint() {
+        let source = r#"
+Broken Game:
+  files:
+    linux:
+      - "{Appdata}/Broken/*"
+      - "{Home}saves/*"
+    windows:
+      - "{AppData}/Broken/[*"
+      - "{AppData}/Broken/**"
+      - "{XDGData}/Broken/*"
+    dos:
+      - "C:/SAVES/*"
+"Broken Game":
+  files:
+    windows:
+      - "{Documents}/Broken/*"
+      - "{Documents}/Broken/{3F2504E0-4F89-11D3-9A0C-0305E82C3301}/*"
+"#;
+
+        let problems: Vec<(usize, Severity)> = lint(source).iter().map(|problem| (problem.line, problem.severity)).collect();
+
+        assert_eq!(
+            problems,
+            [
+                (2, Severity::Error),
+                (5, Severity::Error),
+                (6, Severity::Error),
+                (8, Severity::Error),
+                (9, Severity::Warning),
+                (10, Severity::Warning),
+                (13, Severity::Error)
+            ]
+        );
+    }
+}
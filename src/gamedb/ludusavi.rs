@@ -0,0 +1,275 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use super::{GameDbEntry, GameFiles, StoreIds};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Ludusavi placeholders and their Aletheia equivalents, longest match first. `<storeUserId>` becomes `{StoreUserId}`, the
+/// Steam account in games from Steam and any user elsewhere.
+const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("<root>/userdata/<storeUserId>", "{SteamUserData}"),
+    ("<root>/<game>", "{GameRoot}"),
+    ("<base>", "{GameRoot}"),
+    ("<home>/Saved Games", "{SavedGames}"),
+    ("<home>", "{Home}"),
+    ("<osUserName>", "{OSUserName}"),
+    ("<storeUserId>", "{StoreUserId}"),
+    ("<winAppData>", "{AppData}"),
+    ("<winDocuments>", "{Documents}"),
+    ("<winLocalAppDataLow>", "{LocalLow}"),
+    ("<winLocalAppData>", "{LocalAppData}"),
+    ("<winProgramData>", "{WinProgramData}"),
+    ("<winPublic>", "{WinPublic}"),
+    ("<xdgConfig>", "{XDGConfig}"),
+    ("<xdgData>", "{XDGData}")
+];
+
+/// Stores Aletheia has a scanner for, paths restricted to other stores are dropped.
+const STORES: &[&str] = &["gog", "heroic", "lutris", "microsoft", "steam"];
+
+#[derive(Deserialize)]
+struct Game {
+    alias: Option<String>,
+    #[serde(default)]
+    files: HashMap<String, Option<FileInfo>>,
+    gog: Option<GogInfo>,
+    id: Option<IdInfo>,
+    steam: Option<SteamInfo>
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct FileInfo {
+    tags: Vec<String>,
+    when: Vec<Constraint>
+}
+
+#[derive(Deserialize)]
+struct Constraint {
+    os: Option<String>,
+    store: Option<String>
+}
+
+#[derive(Deserialize)]
+struct GogInfo {
+    id: Option<u64>
+}
+
+#[derive(Deserialize)]
+struct IdInfo {
+    lutris: Option<String>
+}
+
+#[derive(Deserialize)]
+struct SteamInfo {
+    id: Option<u32>
+}
+
+/// Entries converted from a Ludusavi manifest, and how many save paths were dropped for using placeholders Aletheia
+/// doesn't support, such as `<game>` outside of the installation directory.
+pub struct Conversion {
+    pub dropped: usize,
+    pub entries: HashMap<String, GameDbEntry>
+}
+
+#[derive(Default)]
+struct Paths {
+    linux: Vec<String>,
+    mac: Vec<String>,
+    windows: Vec<String>
+}
+
+/// Whether a parsed YAML document is a Ludusavi manifest rather than an Aletheia database.
+pub fn is_manifest(document: &serde_yaml::Value) -> bool {
+    let Some(games) = document.as_mapping() else {
+        return false;
+    };
+
+    let is_system = |key: &str| matches!(key, "linux" | "mac" | "windows");
+    let files = games.values().filter_map(|game| game.get("files")?.as_mapping());
+
+    files.flat_map(serde_yaml::Mapping::keys).filter_map(serde_yaml::Value::as_str).any(|key| !is_system(key))
+}
+
+/// Converts a [Ludusavi manifest](https://github.com/mtkennerly/ludusavi-manifest), keeping save paths that only use
+/// placeholders Aletheia supports.
+pub fn convert(document: serde_yaml::Value) -> Result<Conversion, serde_yaml::Error> {
+    let games: HashMap<String, Game> = serde_yaml::from_value(document)?;
+    let mut db = HashMap::new();
+    let mut dropped = 0;
+    let mut aliases: Vec<(String, String)> = vec![];
+
+    for (name, game) in games {
+        if let Some(target) = game.alias {
+            aliases.push((target, name));
+            continue;
+        }
+
+        let mut paths = Paths::default();
+
+        for (path, info) in game.files {
+            dropped += usize::from(!add_path(&mut paths, &path, &info.unwrap_or_default()));
+        }
+
+        if paths.linux.is_empty() && paths.mac.is_empty() && paths.windows.is_empty() {
+            continue;
+        }
+
+        let ids = StoreIds {
+            gog: game.gog.and_then(|gog| gog.id),
+            lutris: game.id.and_then(|id| id.lutris),
+            steam: game.steam.and_then(|steam| steam.id),
+            ..StoreIds::default()
+        };
+
+        db.insert(name, GameDbEntry { files: paths.into_files(), ids, ..GameDbEntry::default() });
+    }
+
+    for (target, alias) in aliases {
+        if let Some(entry) = db.get_mut(&target) {
+            entry.aliases.push(alias);
+        }
+    }
+
+    Ok(Conversion { dropped, entries: db })
+}
+
+/// Adds a save path to the systems it applies to, returning false if it had to be dropped for using unsupported
+/// placeholders.
+fn add_path(paths: &mut Paths, path: &str, info: &FileInfo) -> bool {
+    if !info.tags.is_empty() && !info.tags.iter().any(|tag| tag == "save") {
+        return true;
+    }
+
+    // A path is usable if it has no constraints or at least one constraint for a store Aletheia scans.
+    let constraints: Vec<&Constraint> =
+        info.when.iter().filter(|when| when.store.as_deref().is_none_or(|store| STORES.contains(&store))).collect();
+
+    if !info.when.is_empty() && constraints.is_empty() {
+        return true;
+    }
+
+    let Some(converted) = convert_path(path) else {
+        log::debug!("Skipping Ludusavi path with unsupported placeholders: {path}");
+        return false;
+    };
+
+    let mut systems: Vec<&str> = constraints.iter().filter_map(|when| when.os.as_deref()).collect();
+
+    // Constraints without an OS apply everywhere the placeholders make sense.
+    if systems.is_empty() || constraints.iter().any(|when| when.os.is_none()) {
+        if path.contains("<win") {
+            systems.push("windows");
+        } else if path.contains("<xdg") {
+            systems.push("linux");
+        } else {
+            systems.extend(["linux", "mac", "windows"]);
+        }
+    }
+
+    for system in systems {
+        let list = match system {
+            "linux" => &mut paths.linux,
+            "mac" => &mut paths.mac,
+            "windows" => &mut paths.windows,
+            _ => continue
+        };
+
+        for converted in &converted {
+            if !list.contains(converted) {
+                list.push(converted.clone());
+            }
+        }
+    }
+
+    true
+}
+
+/// Converts a path to Aletheia's placeholders and the patterns matching the files it stands for.
+///
+/// Ludusavi backs up directories recursively, so paths that don't end in a glob also match every file below them. Names
+/// without an extension are assumed to be directories, names with one could be either.
+fn convert_path(path: &str) -> Option<Vec<String>> {
+    let converted = PLACEHOLDERS.iter().fold(path.replace('\\', "/"), |path, (from, to)| path.replace(from, to));
+    let converted = converted.trim_end_matches('/');
+
+    // Anything left is a placeholder without an equivalent, such as <winDir>, <game> or registry keys
+    if converted.contains('<') {
+        return None;
+    }
+
+    let name = converted.rsplit('/').next().unwrap_or(converted);
+    let contents = format!("{converted}/**/*");
+
+    Some(if name.contains(['*', '?', '[']) {
+        vec![converted.to_owned()]
+    } else if name.contains('.') {
+        vec![converted.to_owned(), contents]
+    } else {
+        vec![contents]
+    })
+}
+
+impl Paths {
+    fn into_files(self) -> GameFiles {
+        let non_empty = |paths: Vec<String>| (!paths.is_empty()).then_some(paths);
+
+        GameFiles {
+            windows: non_empty(self.windows),
+            #[cfg(all(unix, not(target_os = "macos")))]
+            linux: non_empty(self.linux),
+            #[cfg(target_os = "macos")]
+            mac: non_empty(self.mac)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        let manifest = r"
+Celeste:
+  files:
+    <base>/Saves:
+      tags:
+        - save
+    <winDocuments>/<game>/Backups:
+      tags:
+        - save
+    <xdgData>/Celeste/Saves:
+      tags:
+        - save
+      when:
+        - os: linux
+    <root>/<game>/Saves/settings.celeste:
+      tags:
+        - config
+  steam:
+    id: 504230
+";
+
+        let conversion = convert(serde_yaml::from_str(manifest).unwrap()).unwrap();
+        let celeste = &conversion.entries["Celeste"];
+
+        assert_eq!(conversion.dropped, 1);
+        assert_eq!(celeste.ids.steam, Some(504230));
+        assert_eq!(celeste.files.windows.as_deref(), Some(&["{GameRoot}/Saves/**/*".to_owned()][..]));
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            let mut linux = celeste.files.linux.clone().unwrap();
+            linux.sort();
+            assert_eq!(linux, ["{GameRoot}/Saves/**/*", "{XDGData}/Celeste/Saves/**/*"]);
+        }
+
+        assert_eq!(
+            convert_path("<root>/<game>/Saves/settings.celeste").unwrap(),
+            ["{GameRoot}/Saves/settings.celeste", "{GameRoot}/Saves/settings.celeste/**/*"]
+        );
+        assert_eq!(convert_path("<winAppData>/Game/*.sav").unwrap(), ["{AppData}/Game/*.sav"]);
+    }
+}
@@ -0,0 +1,168 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use super::GameDbEntry;
+use crate::scanner::{Game, StoreId};
+use std::collections::HashMap;
+
+/// Trailing words that only describe the edition of a game, stripped when looking for near misses. Phrases are only
+/// stripped as a whole, so "Final Cut" doesn't leave "Final" behind.
+const EDITION_SUFFIXES: &[&str] = &[
+    "anniversary",
+    "complete",
+    "definitive",
+    "deluxe",
+    "digital",
+    "director's cut",
+    "directors cut",
+    "edition",
+    "enhanced",
+    "final cut",
+    "game of the year",
+    "goty",
+    "gold",
+    "hd",
+    "premium",
+    "remastered",
+    "special",
+    "standard",
+    "the director's cut",
+    "the directors cut",
+    "the final cut",
+    "ultimate"
+];
+
+/// Installed game that didn't match any database entry but is close to one.
+pub struct NearMiss {
+    pub candidate: String,
+    pub source: String,
+    pub title: String
+}
+
+/// Lowercases a title and unifies quotes, dashes and whitespace while dropping trademark symbols.
+pub fn normalize_title(title: &str) -> String {
+    let mapped: String = title
+        .chars()
+        .filter(|c| !matches!(c, '™' | '®' | '©'))
+        .map(|c| match c {
+            '‘' | '’' | '‚' | '‛' | '′' | '`' | '´' => '\'',
+            '“' | '”' | '„' | '‟' | '″' => '"',
+            '‐' | '‑' | '‒' | '–' | '—' | '―' | '−' => '-',
+            c => c
+        })
+        .collect();
+
+    mapped.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Removes edition suffixes such as "Game of the Year Edition" or "(Director's Cut)" from a normalised title.
+fn strip_edition(title: &str) -> &str {
+    let mut title = title.trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '-' | '(' | ')'));
+
+    loop {
+        let trimmed = EDITION_SUFFIXES
+            .iter()
+            .filter_map(|suffix| title.strip_suffix(suffix)?.strip_suffix(|c: char| c.is_whitespace() || matches!(c, ':' | '-' | '(')))
+            .min_by_key(|rest| rest.len())
+            .unwrap_or(title);
+
+        let trimmed = trimmed.trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '-' | '(' | ')'));
+        if trimmed.len() == title.len() || trimmed.is_empty() {
+            return title;
+        }
+
+        title = trimmed;
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Index of the game database used to find the entry for an installed game.
+pub struct Matcher<'a> {
+    db: &'a HashMap<String, GameDbEntry>,
+    by_id: HashMap<StoreId, &'a String>,
+    by_title: HashMap<String, &'a String>
+}
+
+impl<'a> Matcher<'a> {
+    pub fn new(db: &'a HashMap<String, GameDbEntry>) -> Self {
+        let by_id = db.iter().flat_map(|(name, entry)| entry.ids.iter().map(move |id| (id, name))).collect();
+        let mut by_title: HashMap<String, &String> = db.keys().map(|name| (normalize_title(name), name)).collect();
+
+        for (name, entry) in db {
+            for alias in &entry.aliases {
+                by_title.entry(normalize_title(alias)).or_insert(name);
+            }
+        }
+
+        Self { db, by_id, by_title }
+    }
+
+    /// Returns the name of the entry for a game, preferring store IDs over exact titles over normalised titles and aliases.
+    pub fn find(&self, game: &Game) -> Option<&'a String> {
+        if let Some(name) = game.store_id.as_ref().and_then(|id| self.by_id.get(id)) {
+            return Some(name);
+        }
+
+        let accepts = |name: &String| {
+            self.db.get(name).is_some_and(|entry| game.store_id.as_ref().is_none_or(|id| !entry.ids.conflicts_with(id)))
+        };
+
+        self.db
+            .get_key_value(&game.name)
+            .map(|(name, _)| name)
+            .filter(|name| accepts(name))
+            .or_else(|| self.by_title.get(&normalize_title(&game.name)).copied().filter(|name| accepts(name)))
+    }
+
+    /// Finds the closest entry for a game that didn't match, by comparing titles without edition suffixes and by edit
+    /// distance.
+    pub fn near_miss(&self, game: &Game) -> Option<NearMiss> {
+        let normalized = normalize_title(&game.name);
+        let stripped = strip_edition(&normalized);
+        let max_distance = (stripped.chars().count() / 10).clamp(1, 3);
+
+        let candidate = self
+            .by_title
+            .iter()
+            .filter_map(|(title, name)| {
+                let distance = edit_distance(stripped, strip_edition(title));
+                (distance <= max_distance).then_some((distance, *name))
+            })
+            .min_by(|(a_distance, a_name), (b_distance, b_name)| a_distance.cmp(b_distance).then_with(|| a_name.cmp(b_name)))?;
+
+        Some(NearMiss { candidate: candidate.1.clone(), source: game.source.clone(), title: game.name.clone() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_normalization() {
+        assert_eq!(normalize_title("AKIBA’S TRIP:  Undead &  Undressed™"), "akiba's trip: undead & undressed");
+        assert_eq!(normalize_title("Tom Clancy’s Ghost Recon® — Wildlands"), "tom clancy's ghost recon - wildlands");
+        assert_eq!(strip_edition("batman: arkham city - game of the year edition"), "batman: arkham city");
+        assert_eq!(strip_edition("disco elysium - the final cut"), "disco elysium");
+        assert_eq!(strip_edition("deus ex: human revolution (director's cut)"), "deus ex: human revolution");
+        assert_eq!(strip_edition("the cut"), "the cut");
+        assert_eq!(strip_edition("edition"), "edition");
+    }
+}
@@ -0,0 +1,72 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use super::{Error, Result};
+use crate::http::HttpClient;
+use minisign_verify::{PublicKey, Signature};
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Key the official game database is signed with. Debug builds without `resources/gamedb.pub` can't update the database.
+#[cfg(gamedb_public_key)]
+const GAMEDB_PUBLIC_KEY: Option<&str> = Some(include_str!("../../resources/gamedb.pub"));
+
+#[cfg(not(gamedb_public_key))]
+const GAMEDB_PUBLIC_KEY: Option<&str> = None;
+
+#[cfg(all(not(gamedb_public_key), not(debug_assertions)))]
+compile_error!("Release builds need the maintainers' GameDB public key in resources/gamedb.pub");
+
+/// Checks a database against its detached minisign signature. `public_key` is either a public key file or the base64 key
+/// on its own.
+pub fn verify(source: &str, contents: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let invalid = |e: minisign_verify::Error| Error::BadSignature(source.to_owned(), e.to_string());
+    let public_key = PublicKey::decode(public_key).or_else(|_| PublicKey::from_base64(public_key.trim())).map_err(invalid)?;
+    let signature = Signature::decode(signature).map_err(invalid)?;
+
+    public_key.verify(contents, &signature, false).map_err(invalid)
+}
+
+/// The key official database updates are verified with, or an error when Aletheia was built without one.
+pub fn official_public_key() -> Result<&'static str> {
+    GAMEDB_PUBLIC_KEY.ok_or(Error::NoPublicKey)
+}
+
+/// Checks the official database against the signature `signature` fetches, returning the version from its trusted comment.
+pub fn verify_official(contents: &[u8], signature: impl FnOnce() -> Result<String>) -> Result<u64> {
+    let public_key = official_public_key()?;
+    let signature = signature()?;
+    verify("GameDB", contents, &signature, public_key)?;
+
+    Signature::decode(&signature)
+        .ok()
+        .and_then(|signature| version(signature.trusted_comment()))
+        .ok_or_else(|| Error::BadSignature("GameDB".to_owned(), "the trusted comment has no version".to_owned()))
+}
+
+/// Version in a trusted comment like `version:1760860000 file:gamedb.yaml`.
+fn version(trusted_comment: &str) -> Option<u64> {
+    trusted_comment.split_whitespace().find_map(|field| field.strip_prefix("version:")?.parse().ok())
+}
+
+/// Downloads the signature published next to a database.
+pub fn fetch(client: &HttpClient, url: &str) -> Result<String> {
+    let response = client.get(&format!("{url}.minisig")).send()?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(Error::Unsigned(url.to_owned()));
+    }
+
+    Ok(response.error_for_status()?.text()?)
+}
+
+/// Reads the signature stored next to a local database file.
+pub fn read(path: &Path) -> Result<String> {
+    let mut signature_path = path.as_os_str().to_owned();
+    signature_path.push(".minisig");
+
+    std::fs::read_to_string(signature_path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => Error::Unsigned(path.display().to_string()),
+        _ => e.into()
+    })
+}
@@ -0,0 +1,124 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0} hook exited with {1}")]
+    Failed(Hook, ExitStatus),
+    #[error("Failed to run {0} hook: {1}")]
+    Io(Hook, io::Error)
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Shell commands ran around backups and restores.
+///
+/// Hooks receive `ALETHEIA_HOOK`, `ALETHEIA_GAME`, `ALETHEIA_SOURCE` and `ALETHEIA_ARCHIVE` as environment variables, post hooks also
+/// receive `ALETHEIA_RESULT` and `ALETHEIA_ERROR` if the operation failed. A pre hook exiting with a non-zero status aborts the operation.
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Hooks {
+    pub pre_backup: Option<String>,
+    pub post_backup: Option<String>,
+    pub pre_restore: Option<String>,
+    pub post_restore: Option<String>
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Hook {
+    PreBackup,
+    PostBackup,
+    PreRestore,
+    PostRestore
+}
+
+impl std::fmt::Display for Hook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::PreBackup => "pre_backup",
+            Self::PostBackup => "post_backup",
+            Self::PreRestore => "pre_restore",
+            Self::PostRestore => "post_restore"
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Context<'a> {
+    pub game: &'a str,
+    pub source: &'a str,
+    pub archive: &'a Path,
+    pub result: Option<&'a str>,
+    pub error: Option<&'a str>
+}
+
+impl Hooks {
+    const fn get(&self, hook: Hook) -> Option<&String> {
+        match hook {
+            Hook::PreBackup => self.pre_backup.as_ref(),
+            Hook::PostBackup => self.post_backup.as_ref(),
+            Hook::PreRestore => self.pre_restore.as_ref(),
+            Hook::PostRestore => self.post_restore.as_ref()
+        }
+    }
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]);
+    shell
+}
+
+/// Keeps `cmd` from opening a console window when Aletheia runs without one.
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", command]).creation_flags(CREATE_NO_WINDOW);
+    shell
+}
+
+/// Runs the global hook followed by the game's own hook, stopping at the first failure.
+pub fn run(config: &Config, hook: Hook, context: &Context) -> Result<()> {
+    let hooks = [Some(&config.hooks), config.game_hooks.get(context.game)];
+    let commands = hooks.into_iter().flatten().filter_map(|hooks| hooks.get(hook));
+
+    for command in commands {
+        log::info!("Running {hook} hook for {}: {command}", context.game);
+
+        // Hook output goes to stderr so it can't end up in the JSON lines printed on stdout.
+        let mut process = shell(command);
+        process
+            .stdout(io::stderr())
+            .env("ALETHEIA_HOOK", hook.to_string())
+            .env("ALETHEIA_GAME", context.game)
+            .env("ALETHEIA_SOURCE", context.source)
+            .env("ALETHEIA_ARCHIVE", context.archive);
+
+        if let Some(result) = context.result {
+            process.env("ALETHEIA_RESULT", result);
+        }
+
+        if let Some(error) = context.error {
+            process.env("ALETHEIA_ERROR", error);
+        }
+
+        let status = process.status().map_err(|e| Error::Io(hook, e))?;
+        if !status.success() {
+            return Err(Error::Failed(hook, status));
+        }
+    }
+
+    Ok(())
+}
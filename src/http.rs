@@ -0,0 +1,107 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::{Certificate, Proxy};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to load CA certificate {}: {}", .0.display(), .1)]
+    Certificate(PathBuf, String),
+    #[error("Failed to create HTTP client: {0}")]
+    Client(#[from] reqwest::Error),
+    #[error("Invalid proxy {0}: {1}")]
+    Proxy(String, reqwest::Error)
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Network settings shared by every request Aletheia makes.
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    /// URL the official game database and its signature are downloaded from.
+    pub base_url: String,
+    /// PEM encoded certificates trusted in addition to the system ones.
+    pub ca_certificates: Vec<PathBuf>,
+    /// Copies of `base_url` tried in order when it can't be reached.
+    pub mirrors: Vec<String>,
+    /// Proxy for all requests, otherwise the proxy from the environment is used.
+    pub proxy: Option<String>,
+    /// GitHub API URL of the repository releases are checked against.
+    pub releases_url: String,
+    pub timeout_secs: u64
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://raw.githubusercontent.com/Spencer-0003/aletheia/refs/heads/master/resources".to_owned(),
+            ca_certificates: vec![],
+            mirrors: vec![],
+            proxy: None,
+            releases_url: "https://api.github.com/repos/Spencer-0003/aletheia/releases".to_owned(),
+            timeout_secs: 30
+        }
+    }
+}
+
+/// Client configured from [`HttpConfig`].
+pub struct HttpClient {
+    bases: Vec<String>,
+    client: Client
+}
+
+impl HttpClient {
+    pub fn new(config: &HttpConfig) -> Result<Self> {
+        let mut builder = Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(Duration::from_secs(config.timeout_secs.min(10)))
+            .timeout(Duration::from_secs(config.timeout_secs));
+
+        if let Some(ref proxy) = config.proxy {
+            builder = builder.proxy(Proxy::all(proxy).map_err(|e| Error::Proxy(proxy.clone(), e))?);
+        }
+
+        let certificates = config
+            .ca_certificates
+            .iter()
+            .map(|path| {
+                let pem = std::fs::read(path).map_err(|e| Error::Certificate(path.clone(), e.to_string()))?;
+                Certificate::from_pem(&pem).map_err(|e| Error::Certificate(path.clone(), e.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let bases =
+            std::iter::once(&config.base_url).chain(&config.mirrors).map(|base| base.trim_end_matches('/').to_owned()).collect();
+
+        Ok(Self { bases, client: builder.tls_certs_merge(certificates).build()? })
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    /// Runs `request` against the base URL and then each mirror in turn until one succeeds, returning the last error if
+    /// none do.
+    pub fn with_mirrors<T, E: std::fmt::Display>(
+        &self, mut request: impl FnMut(&str) -> core::result::Result<T, E>
+    ) -> core::result::Result<T, E> {
+        let mut bases = self.bases.iter().peekable();
+
+        loop {
+            let base = bases.next().expect("HttpClient always has a base URL");
+
+            match request(base) {
+                Ok(result) => return Ok(result),
+                Err(e) if bases.peek().is_some() => log::warn!("Request to {base} failed, trying the next mirror: {e}"),
+                Err(e) => return Err(e)
+            }
+        }
+    }
+}
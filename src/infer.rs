@@ -1,13 +1,27 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
-mod backup;
 mod launchers;
-mod restore;
 
-pub use backup::backup;
-pub use restore::restore;
+use crate::config::Config;
+use crate::scanner::Game;
+use launchers::Heroic;
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use launchers::Lutris;
 
 pub trait Launcher {
-    fn get_game() -> Option<crate::scanner::Game>;
+    fn get_game(config: &Config) -> Option<Game>;
+}
+
+pub fn get_game(launcher: &str, config: &Config) -> Option<Game> {
+    match launcher.to_lowercase().as_str() {
+        "heroic" => Heroic::get_game(config),
+        #[cfg(all(unix, not(target_os = "macos")))]
+        "lutris" => Lutris::get_game(config),
+        _ => {
+            log::warn!("Infer was ran using an unsupported launcher.");
+            None
+        }
+    }
 }
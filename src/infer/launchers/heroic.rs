@@ -1,6 +1,7 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
+use crate::config::Config;
 use crate::gamedb;
 use crate::infer::Launcher;
 use crate::scanner::Game;
@@ -8,7 +9,7 @@ use crate::scanner::Game;
 pub struct Heroic;
 
 impl Launcher for Heroic {
-    fn get_game() -> Option<Game> {
+    fn get_game(config: &Config) -> Option<Game> {
         let Ok(game_name) = std::env::var("HEROIC_GAME_TITLE") else {
             log::error!("HEROIC_GAME_TITLE environment variable not found, is the game being launched by Heroic?");
             return None;
@@ -24,6 +25,6 @@ impl Launcher for Heroic {
             return None;
         }
 
-        gamedb::get_installed_games().into_iter().find(|game| game.name == game_name && game.source == "Heroic")
+        gamedb::get_installed_games(config).into_iter().find(|game| game.name == game_name && game.source == "Heroic")
     }
 }
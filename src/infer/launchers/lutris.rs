@@ -1,6 +1,7 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
+use crate::config::Config;
 use crate::gamedb;
 use crate::infer::Launcher;
 use crate::scanner::Game;
@@ -8,12 +9,12 @@ use crate::scanner::Game;
 pub struct Lutris;
 
 impl Launcher for Lutris {
-    fn get_game() -> Option<Game> {
+    fn get_game(config: &Config) -> Option<Game> {
         let Ok(game_name) = std::env::var("GAME_NAME") else {
             log::error!("GAME_NAME environment variable not found, is the game being launched by Lutris?");
             return None;
         };
 
-        gamedb::get_installed_games().into_iter().find(|game| game.name == game_name && game.source == "Lutris")
+        gamedb::get_installed_games(config).into_iter().find(|game| game.name == game_name && game.source == "Lutris")
     }
 }
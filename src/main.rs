@@ -18,6 +18,8 @@ mod config;
 mod dirs;
 mod file;
 mod gamedb;
+mod hooks;
+mod http;
 mod infer;
 mod migrate;
 mod operations;
@@ -28,9 +30,11 @@ mod utils;
 #[cfg(all(feature = "updater", not(debug_assertions)))]
 mod updater;
 
+use cli_helpers::Outcome;
 use commands::{Args, Command};
+use std::process::ExitCode;
 
-fn main() {
+fn main() -> ExitCode {
     env_logger::init();
 
     #[cfg(all(unix, not(target_os = "macos")))]
@@ -66,22 +70,31 @@ fn main() {
 
         if cmd.ends_with(".aletheia") {
             ui::run_restore_dialog(&cfg, &cmd);
-            return;
+            return ExitCode::SUCCESS;
         }
 
         let args = Args::parse(args);
-        match cmd.as_str() {
+        let outcome = match cmd.as_str() {
             "backup" => commands::Backup::run(args, &cfg),
+            "gamedb" => commands::GameDb::run(args, &cfg),
+            "near_misses" => commands::NearMisses::run(args, &cfg),
             "restore" => commands::Restore::run(args, &cfg),
             #[cfg(all(feature = "updater", not(debug_assertions)))]
-            "update" => commands::Update::run(args, &config),
+            "update" => commands::Update::run(args, &cfg),
             "update_gamedb" => commands::UpdateGameDb::run(args, &cfg),
             "update_custom_gamedbs" => commands::UpdateCustom::run(args, &cfg),
-            _ => eprintln!("Command not found.")
-        }
+            _ => {
+                eprintln!("Command not found.");
+                Outcome::Failure
+            }
+        };
+
+        return outcome.into();
     } else if let Some(ref cfg) = config {
         ui::run(cfg);
     } else {
         ui::run_first_time_setup();
     }
+
+    ExitCode::SUCCESS
 }
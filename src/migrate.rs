@@ -45,7 +45,7 @@ pub fn run(config: &Config) {
                 continue;
             }
 
-            writer.add_file(&file.path, &file_path, file.hash.clone());
+            writer.add_file(Path::new(&file.path), None, &file_path);
         }
 
         if missing {
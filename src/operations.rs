@@ -4,6 +4,7 @@
 mod backup;
 mod restore;
 
-pub use backup::backup_game;
+pub use backup::Error as BackupError;
+pub use backup::{BackupReport, Result as BackupResult, backup_games, default_jobs};
 pub use restore::Error as RestoreError;
-pub use restore::restore_game;
+pub use restore::{RestoreReport, Result as RestoreResult, restore_game, source_account};
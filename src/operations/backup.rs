@@ -1,32 +1,104 @@
 // SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
-use crate::archive::{ArchiveReader, ArchiveWriter};
+use crate::archive::{self, ArchiveReader, ArchiveWriter, Error as ArchiveError, FileEntry};
 use crate::config::Config;
-use crate::dirs::{expand_path, shrink_path};
+use crate::dirs::{ReadError, expand_path, glob_save_path, shrink_path};
 use crate::file::hash_file;
 use crate::gamedb::GameDbEntry;
-use crate::scanner::Game;
+use crate::hooks::{self, Hook};
+use crate::scanner::{Game, SteamScanner};
 use crate::utils;
-use glob::glob;
-use std::fs::create_dir_all;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::fs::{create_dir_all, remove_file, rename};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error("Failed to write archive: {0}")]
+    ArchiveWrite(#[from] ArchiveError),
     #[error("Failed to create backup directory: {0}")]
-    DirectoryCreation(#[from] std::io::Error)
+    DirectoryCreation(std::io::Error),
+    #[error("File disappeared during backup: {}", .0.display())]
+    FileDisappeared(PathBuf),
+    #[error("Hook failed: {0}")]
+    Hook(#[from] hooks::Error),
+    #[error("Invalid glob pattern {0}: {1}")]
+    InvalidPattern(String, glob::PatternError),
+    #[error("Permission denied: {}", .0.display())]
+    PermissionDenied(PathBuf),
+    #[error("{0} isn't in the GameDB")]
+    UnknownGame(String),
+    #[error("Failed to read {}: {}", .0.display(), .1)]
+    Unreadable(PathBuf, std::io::Error),
+    #[error("Archive verification failed, kept the previous backup: {0}")]
+    VerificationFailed(String)
+}
+
+impl Error {
+    fn from_io(path: PathBuf, error: std::io::Error) -> Self {
+        match error.kind() {
+            ErrorKind::NotFound => Self::FileDisappeared(path),
+            ErrorKind::PermissionDenied => Self::PermissionDenied(path),
+            _ => Self::Unreadable(path, error)
+        }
+    }
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
 
-pub fn backup_game(game: &Game, config: &Config, entry: &GameDbEntry) -> Result<bool> {
-    let steam_id = config.steam_account_id.as_deref();
-    let backup_folder = config.save_dir.join(utils::sanitize_game_name(&game.name).as_ref());
-    let archive_path = backup_folder.join("backup.aletheia");
-    let previous_archive = archive_path.exists().then(|| ArchiveReader::open(&archive_path).ok()).flatten();
+/// Placeholders that resolve differently for each Steam account, along with `{StoreUserId}` in games from Steam.
+const ACCOUNT_PLACEHOLDERS: &[&str] = &["{SteamID3}", "{SteamID64}", "{SteamUserData}"];
 
-    let mut changed = false;
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: Error
+}
+
+#[derive(Default)]
+pub struct BackupReport {
+    pub bytes: u64,
+    pub changed: bool,
+    pub files: usize,
+    pub skipped: Vec<SkippedFile>,
+    pub verified: bool
+}
+
+impl BackupReport {
+    fn skip(&mut self, game: &str, path: PathBuf, reason: Error) {
+        log::warn!("Skipping {} while backing up {game}: {reason}", path.display());
+        self.skipped.push(SkippedFile { path, reason });
+    }
+}
+
+/// Steam accounts to back up saves for, the account chosen in the settings or otherwise every account that signed in on
+/// this machine.
+fn steam_accounts(config: &Config) -> Vec<String> {
+    if let Some(ref id3) = config.steam_account_id {
+        return vec![id3.clone()];
+    }
+
+    let mut accounts: Vec<String> = SteamScanner::get_users()
+        .into_iter()
+        .flatten()
+        .filter_map(|(id64, _)| id64.parse().ok())
+        .map(|id64| SteamScanner::id64_to_id3(id64).to_string())
+        .collect();
+
+    accounts.sort_unstable();
+    accounts
+}
+
+/// Finds the files to back up along with the Steam account they were found for, which is only recorded for paths that
+/// differ between accounts.
+fn find_files(
+    game: &Game, entry: &GameDbEntry, steam_accounts: &[String], report: &mut BackupReport
+) -> Vec<(PathBuf, Option<String>)> {
     let mut paths = vec![];
 
     #[cfg(windows)]
@@ -52,65 +124,257 @@ pub fn backup_game(game: &Game, config: &Config, entry: &GameDbEntry) -> Result<
     }
 
     let mut files = vec![];
+    let mut seen = HashSet::new();
+    let steam_game = game.source == "Steam";
+    let per_account = |path: &str| {
+        ACCOUNT_PLACEHOLDERS.iter().any(|placeholder| path.contains(placeholder)) || (steam_game && path.contains("{StoreUserId}"))
+    };
 
-    for path in paths {
+    for (path, steam_id) in paths.into_iter().flat_map(|path| {
+        if steam_accounts.is_empty() || !per_account(path) {
+            vec![(path, None)]
+        } else {
+            steam_accounts.iter().map(|account| (path, Some(account.as_str()))).collect()
+        }
+    }) {
         #[cfg(unix)]
-        let expanded = expand_path(Path::new(path), game.installation_dir.as_deref(), game.prefix.as_deref(), steam_id);
+        let expanded = expand_path(Path::new(path), game.installation_dir.as_deref(), game.prefix.as_deref(), steam_id, steam_game);
 
         #[cfg(windows)]
-        let expanded = expand_path(Path::new(path), game.installation_dir.as_deref(), steam_id);
+        let expanded = expand_path(Path::new(path), game.installation_dir.as_deref(), steam_id, steam_game);
 
-        let found_paths = glob(&expanded.to_string_lossy()).unwrap();
+        #[cfg(unix)]
+        let found_paths = glob_save_path(&expanded, game.prefix.as_deref());
+
+        #[cfg(windows)]
+        let found_paths = glob_save_path(&expanded);
+
+        let found_paths = match found_paths {
+            Ok(found_paths) => found_paths,
+            Err(e) => {
+                report.skip(&game.name, expanded, Error::InvalidPattern(path.clone(), e));
+                continue;
+            }
+        };
 
         for file in found_paths {
-            let file = file.unwrap();
+            let file = match file {
+                Ok(file) => file,
+                Err(ReadError { error, path }) => {
+                    report.skip(&game.name, path.clone(), Error::from_io(path, error));
+                    continue;
+                }
+            };
 
             if file.is_dir() {
                 log::warn!("Found {} while backing up {}. Glob patterns should match files only.", file.display(), game.name);
                 continue;
             }
 
-            if file.file_name().unwrap() == "steam_autocloud.vdf" {
+            if file.file_name().is_some_and(|name| name == "steam_autocloud.vdf") || !seen.insert(file.clone()) {
                 continue;
             }
 
-            files.push(file);
+            files.push((file, steam_id.map(ToOwned::to_owned)));
         }
     }
 
+    files
+}
+
+pub fn backup_game(game: &Game, config: &Config, entry: &GameDbEntry) -> Result<BackupReport> {
+    let backup_folder = config.save_dir.join(utils::sanitize_game_name(&game.name).as_ref());
+    let archive_path = backup_folder.join("backup.aletheia");
+    let context = hooks::Context { game: &game.name, source: &game.source, archive: &archive_path, result: None, error: None };
+
+    hooks::run(config, Hook::PreBackup, &context)?;
+
+    let result = perform_backup(game, config, entry, &backup_folder, &archive_path);
+    let error = result.as_ref().err().map(ToString::to_string);
+    let status = match result {
+        Ok(ref report) if !report.skipped.is_empty() => "partial",
+        Ok(ref report) if report.changed => "success",
+        Ok(_) => "unchanged",
+        Err(_) => "failed"
+    };
+
+    let context = hooks::Context { result: Some(status), error: error.as_deref(), ..context };
+    if let Err(e) = hooks::run(config, Hook::PostBackup, &context) {
+        log::warn!("{e}");
+    }
+
+    result
+}
+
+/// Number of games backed up at once when the user doesn't ask for a specific amount.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, std::num::NonZero::get)
+}
+
+/// Backs up every game on a pool of at most `jobs` worker threads.
+///
+/// `on_done` is called on the calling thread as each game finishes, in completion order, so callers can report progress and
+/// aggregate the results. Games missing from `game_db`, such as after the game database changed since they were scanned, fail
+/// with [`Error::UnknownGame`].
+pub fn backup_games(
+    games: &[Game], game_db: &HashMap<String, GameDbEntry>, config: &Config, jobs: usize,
+    mut on_done: impl FnMut(&Game, Result<BackupReport>, Duration)
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, games.len().max(1)) {
+            let sender = sender.clone();
+            let next = &next;
+
+            scope.spawn(move || {
+                while let Some(game) = games.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let start = Instant::now();
+                    let result = game_db
+                        .get(&game.name)
+                        .ok_or_else(|| Error::UnknownGame(game.name.clone()))
+                        .and_then(|entry| backup_game(game, config, entry));
+
+                    if sender.send((game, result, start.elapsed())).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(sender);
+
+        for (game, result, elapsed) in receiver {
+            on_done(game, result, elapsed);
+        }
+    });
+}
+
+fn perform_backup(
+    game: &Game, config: &Config, entry: &GameDbEntry, backup_folder: &Path, archive_path: &Path
+) -> Result<BackupReport> {
+    // Paranoid backups verify the previous archive's data instead of trusting its index. Archives in an older format are
+    // ignored so they get rewritten.
+    let previous_files = if !archive_path.exists() {
+        None
+    } else if config.paranoid_backups {
+        ArchiveReader::open(archive_path).ok().map(|reader| reader.files)
+    } else {
+        ArchiveReader::read_index(archive_path).ok().filter(|index| index.version == archive::VERSION).map(|index| index.files)
+    };
+
+    let mut report = BackupReport::default();
+    let files = find_files(game, entry, &steam_accounts(config), &mut report);
+
     if files.is_empty() {
-        return Ok(false);
+        return Ok(report);
     }
 
-    create_dir_all(&backup_folder)?;
+    create_dir_all(backup_folder).map_err(Error::DirectoryCreation)?;
+
+    let temp_path = archive_path.with_extension("aletheia.tmp");
+    let mut writer = ArchiveWriter::new(game.name.clone(), &temp_path);
+    let mut changed = false;
+    let mut bytes = 0;
+    let mut file_count = 0;
+    let steam_game = game.source == "Steam";
 
-    let mut writer = ArchiveWriter::new(game.name.clone(), &archive_path);
+    for (file, steam_account) in files {
+        let steam_id = steam_account.as_deref();
 
-    for file in files {
         #[cfg(unix)]
-        let shrunk_file = shrink_path(file.as_path(), game.installation_dir.as_deref(), game.prefix.as_deref(), steam_id);
+        let shrunk_file = shrink_path(file.as_path(), game.installation_dir.as_deref(), game.prefix.as_deref(), steam_id, steam_game);
 
         #[cfg(windows)]
-        let shrunk_file = shrink_path(file.as_path(), game.installation_dir.as_deref(), steam_id);
+        let shrunk_file = shrink_path(file.as_path(), game.installation_dir.as_deref(), steam_id, steam_game);
 
-        let shrunk_file_path = shrunk_file.to_string_lossy();
-        let file_hash = hash_file(&file);
+        let metadata = match file.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                report.skip(&game.name, file.clone(), Error::from_io(file, e));
+                continue;
+            }
+        };
 
-        let file_changed = previous_archive
+        let previous = previous_files
             .as_ref()
-            .and_then(|archive| archive.files.iter().find(|e| e.shrunk_path == shrunk_file_path))
-            .is_none_or(|existing| existing.checksum != file_hash);
+            .and_then(|files| files.iter().find(|e| e.path() == shrunk_file && e.steam_account == steam_account));
+        // The previous checksum only decides whether anything changed, the archive hashes the data it actually stores.
+        let file_hash = match previous {
+            Some(existing)
+                if !config.paranoid_backups
+                    && existing.size == metadata.len()
+                    && metadata.modified().is_ok_and(|modified| modified == existing.modified) =>
+            {
+                Ok(existing.checksum.clone())
+            }
+            _ => hash_file(&file)
+        };
 
-        changed |= file_changed;
+        let file_hash = match file_hash {
+            Ok(file_hash) => file_hash,
+            Err(e) => {
+                report.skip(&game.name, file.clone(), Error::from_io(file, e));
+                continue;
+            }
+        };
+
+        changed |= previous.is_none_or(|existing| existing.checksum != file_hash);
+        bytes += metadata.len();
+        file_count += 1;
 
-        writer.add_file(&shrunk_file_path, &file, file_hash);
+        writer.add_file(&shrunk_file, steam_id, &file);
     }
 
     if !changed {
-        return Ok(false);
+        return Ok(report);
+    }
+
+    let written = match writer.finalize() {
+        Ok(written) => written,
+        Err(e) => {
+            remove_file(&temp_path).ok();
+            return Err(e.into());
+        }
+    };
+
+    if config.verify_backups {
+        if let Err(e) = verify_archive(&temp_path, &written) {
+            remove_file(&temp_path).ok();
+            return Err(e);
+        }
+
+        report.verified = true;
+    }
+
+    if let Err(e) = rename(&temp_path, archive_path) {
+        remove_file(&temp_path).ok();
+        return Err(Error::ArchiveWrite(e.into()));
     }
 
-    writer.finalize().unwrap();
+    report.bytes = bytes;
+    report.changed = true;
+    report.files = file_count;
+
+    Ok(report)
+}
+
+/// Reopens a freshly written archive and checks every entry against the hashes taken while writing it.
+fn verify_archive(path: &Path, expected: &[FileEntry]) -> Result<()> {
+    let reader = ArchiveReader::open(path).map_err(|e| Error::VerificationFailed(e.to_string()))?;
+
+    if reader.files.len() != expected.len() {
+        return Err(Error::VerificationFailed(format!("expected {} files, found {}", expected.len(), reader.files.len())));
+    }
+
+    for written in expected {
+        match reader.files.iter().find(|e| e.path() == written.path() && e.steam_account == written.steam_account) {
+            Some(entry) if entry.checksum == written.checksum => {}
+            Some(_) => return Err(Error::VerificationFailed(format!("checksum mismatch for {}", written.shrunk_path))),
+            None => return Err(Error::VerificationFailed(format!("{} is missing", written.shrunk_path)))
+        }
+    }
 
-    Ok(true)
+    Ok(())
 }
@@ -1,26 +1,53 @@
 // SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
-use crate::archive::{ArchiveReader, Error as ArchiveError};
+use crate::archive::{ArchiveReader, Error as ArchiveError, FileEntry, steam_accounts};
 use crate::config::Config;
-use crate::dirs::expand_path;
+use crate::dirs::{expand_path, unresolved_placeholder};
+use crate::hooks::{self, Hook};
 use crate::scanner::Game;
 use crate::utils::sanitize_game_name;
 use std::fs::create_dir_all;
 use std::path::Path;
 
+#[cfg(unix)]
+use crate::dirs::match_existing_case;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Archive error: {0}")]
     Archive(#[from] ArchiveError),
+    #[error("Hook failed: {0}")]
+    Hook(#[from] hooks::Error),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Backup has no saves for Steam account {0}")]
+    MissingAccount(String),
+    #[error("Backup has saves for several Steam accounts ({0}), choose the account to restore from")]
+    MultipleAccounts(String),
     #[error("No backups found")]
-    NoBackupsFound
+    NoBackupsFound,
+    #[error("{} can't be restored without the game installed or a Wine prefix ({} is unknown)", .1.join(", "), .0)]
+    UnresolvedPlaceholder(String, Vec<String>)
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
 
-pub fn restore_game(game: &Game, config: &Config) -> Result<()> {
-    let steam_id = config.steam_account_id.as_deref();
+#[derive(Default)]
+pub struct RestoreReport {
+    pub bytes: u64,
+    pub files: usize,
+    /// Steam account the saves were backed up from, when it isn't the account they were restored to.
+    pub remapped_from: Option<String>,
+    /// Saves that weren't restored because their path depends on the game being installed or a Wine prefix.
+    pub skipped: Vec<String>
+}
+
+/// Restores the latest backup of a game, putting Steam account specific saves into `steam_account` or the account from
+/// the config when it's `None`. `from_steam_account` picks whose saves are restored from a backup of several accounts.
+pub fn restore_game(
+    game: &Game, config: &Config, steam_account: Option<&str>, from_steam_account: Option<&str>
+) -> Result<RestoreReport> {
     let backup_folder = config.save_dir.join(sanitize_game_name(&game.name).as_ref());
     let archive_path = backup_folder.join("backup.aletheia");
 
@@ -29,19 +56,176 @@ pub fn restore_game(game: &Game, config: &Config) -> Result<()> {
         return Err(Error::NoBackupsFound);
     }
 
-    let mut reader = ArchiveReader::open(&archive_path)?;
-    for entry in &reader.files.clone() {
+    let context = hooks::Context { game: &game.name, source: &game.source, archive: &archive_path, result: None, error: None };
+
+    hooks::run(config, Hook::PreRestore, &context)?;
+
+    let result = perform_restore(game, &archive_path, steam_account.or(config.steam_account_id.as_deref()), from_steam_account);
+    let error = result.as_ref().err().map(ToString::to_string);
+    let status = match result {
+        Ok(_) => "success",
+        Err(_) => "failed"
+    };
+
+    let context = hooks::Context { result: Some(status), error: error.as_deref(), ..context };
+    if let Err(e) = hooks::run(config, Hook::PostRestore, &context) {
+        log::warn!("{e}");
+    }
+
+    result
+}
+
+/// Picks the Steam account whose saves are restored: `from` when given, the target account if the archive has saves for
+/// it, otherwise the only account in the archive.
+pub fn source_account<'a>(files: &'a [FileEntry], target: Option<&str>, from: Option<&str>) -> Result<Option<&'a str>> {
+    let accounts = steam_accounts(files);
+
+    if let Some(from) = from {
+        return accounts.into_iter().find(|account| *account == from).map(Some).ok_or_else(|| Error::MissingAccount(from.to_owned()));
+    }
+
+    match target.and_then(|target| accounts.iter().find(|account| **account == target)) {
+        Some(account) => Ok(Some(account)),
+        None if accounts.len() <= 1 => Ok(accounts.first().copied()),
+        None => Err(Error::MultipleAccounts(accounts.join(", ")))
+    }
+}
+
+fn perform_restore(game: &Game, archive_path: &Path, target: Option<&str>, from: Option<&str>) -> Result<RestoreReport> {
+    let mut report = RestoreReport::default();
+    let mut reader = ArchiveReader::open(archive_path)?;
+    let mut destinations = Vec::with_capacity(reader.files.len());
+    let source = source_account(&reader.files, target, from)?.map(ToOwned::to_owned);
+
+    // Saves of the source account are remapped to the target account, or restored to the same account without one.
+    let steam_id = target.or(source.as_deref());
+    if let (Some(source), Some(target)) = (source.as_deref(), target)
+        && source != target
+    {
+        log::warn!("Restoring saves of Steam account {source} to account {target}");
+        report.remapped_from = Some(source.to_owned());
+    }
+
+    // Every path is expanded up front so nothing is written if none of them can be resolved.
+    let mut unresolved = None;
+    for entry in reader.files.iter().filter(|entry| entry.steam_account.is_none() || entry.steam_account == source) {
+        // Only paths backed up for a Steam account can contain `{StoreUserId}`, even when the game isn't installed anymore
+        let steam_game = entry.steam_account.is_some();
+
         #[cfg(unix)]
-        let expanded = expand_path(Path::new(&entry.shrunk_path), game.installation_dir.as_deref(), game.prefix.as_deref(), steam_id);
+        let expanded = expand_path(&entry.path(), game.installation_dir.as_deref(), game.prefix.as_deref(), steam_id, steam_game);
 
         #[cfg(windows)]
-        let expanded = expand_path(Path::new(&entry.shrunk_path), game.installation_dir.as_deref(), steam_id);
+        let expanded = expand_path(&entry.path(), game.installation_dir.as_deref(), steam_id, steam_game);
+
+        if let Some(placeholder) = unresolved_placeholder(&expanded) {
+            log::warn!("Skipping {}, {placeholder} is unknown", entry.shrunk_path);
+            report.skipped.push(entry.shrunk_path.clone());
+            unresolved.get_or_insert(placeholder);
+            continue;
+        }
+
+        #[cfg(unix)]
+        let expanded = match_existing_case(&expanded, game.prefix.as_deref());
 
-        create_dir_all(expanded.parent().unwrap()).unwrap();
-        reader.extract_file(&entry.shrunk_path, &expanded).unwrap();
+        destinations.push((entry.path(), entry.steam_account.clone(), expanded));
+    }
+
+    if let Some(placeholder) = unresolved
+        && destinations.is_empty()
+    {
+        return Err(Error::UnresolvedPlaceholder(placeholder, report.skipped));
+    }
+
+    for (shrunk_path, steam_account, expanded) in destinations {
+        if let Some(parent) = expanded.parent() {
+            create_dir_all(parent)?;
+        }
+
+        report.bytes += reader.extract_file(&shrunk_path, steam_account.as_deref(), &expanded)?;
+        report.files += 1;
 
         log::info!("Restored: {}", expanded.display());
     }
 
-    Ok(())
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::ArchiveWriter;
+    use crate::scanner::SteamScanner;
+    use std::fs::{read_to_string, remove_dir_all, write};
+
+    #[test]
+    fn test_restore_braced_directory() {
+        let temp = std::env::temp_dir().join("aletheia-restore-braced");
+        let game = Game { installation_dir: Some(temp.join("game")), ..Game::uninstalled("Braced Test".into()) };
+        let config = Config { save_dir: temp.join("saves"), ..Config::default() };
+        let backup_folder = config.save_dir.join(sanitize_game_name(&game.name).as_ref());
+        let source = temp.join("save.dat");
+
+        create_dir_all(&backup_folder).unwrap();
+        write(&source, "Save").unwrap();
+
+        let mut writer = ArchiveWriter::new(game.name.clone(), &backup_folder.join("backup.aletheia"));
+        writer.add_file(Path::new("{GameRoot}/Saves/{3F2504E0-4F89-11D3-9A0C-0305E82C3301}/save.dat"), None, &source);
+        writer.finalize().unwrap();
+
+        assert_eq!(restore_game(&game, &config, None, None).unwrap().files, 1);
+        assert_eq!(read_to_string(temp.join("game/Saves/{3F2504E0-4F89-11D3-9A0C-0305E82C3301}/save.dat")).unwrap(), "Save");
+
+        remove_dir_all(temp).unwrap();
+    }
+
+    #[test]
+    fn test_restore_skips_unresolved() {
+        let temp = std::env::temp_dir().join("aletheia-restore-skips");
+        let game = Game::uninstalled("Skip Test".into());
+        let config = Config { save_dir: temp.join("saves"), ..Config::default() };
+        let backup_folder = config.save_dir.join(sanitize_game_name(&game.name).as_ref());
+        let source = temp.join("save.dat");
+
+        create_dir_all(&backup_folder).unwrap();
+        write(&source, "Save").unwrap();
+
+        let mut writer = ArchiveWriter::new(game.name.clone(), &backup_folder.join("backup.aletheia"));
+        writer.add_file(Path::new("{GameRoot}/Saves/save.dat"), None, &source);
+        writer.add_file(&temp.join("restored/save.dat"), None, &source);
+        writer.finalize().unwrap();
+
+        let report = restore_game(&game, &config, None, None).unwrap();
+        assert_eq!(report.files, 1);
+        assert_eq!(report.skipped, ["{GameRoot}/Saves/save.dat"]);
+        assert_eq!(read_to_string(temp.join("restored/save.dat")).unwrap(), "Save");
+
+        remove_dir_all(temp).unwrap();
+    }
+
+    #[test]
+    fn test_restore_from_steam_account() {
+        let temp = std::env::temp_dir().join("aletheia-restore-from-account");
+        let game = Game { installation_dir: Some(temp.join("game")), ..Game::uninstalled("Shared PC Test".into()) };
+        let config = Config { save_dir: temp.join("saves"), ..Config::default() };
+        let backup_folder = config.save_dir.join(sanitize_game_name(&game.name).as_ref());
+
+        create_dir_all(&backup_folder).unwrap();
+        write(temp.join("first.dat"), "First").unwrap();
+        write(temp.join("second.dat"), "Second").unwrap();
+
+        let mut writer = ArchiveWriter::new(game.name.clone(), &backup_folder.join("backup.aletheia"));
+        writer.add_file(Path::new("{GameRoot}/{StoreUserId}/save.dat"), Some("111"), &temp.join("first.dat"));
+        writer.add_file(Path::new("{GameRoot}/{StoreUserId}/save.dat"), Some("222"), &temp.join("second.dat"));
+        writer.finalize().unwrap();
+
+        assert!(matches!(restore_game(&game, &config, Some("333"), None), Err(Error::MultipleAccounts(_))));
+
+        let report = restore_game(&game, &config, Some("333"), Some("222")).unwrap();
+        assert_eq!(report.remapped_from.as_deref(), Some("222"));
+        let id64 = SteamScanner::id3_to_id64(333);
+        assert_eq!(read_to_string(temp.join(format!("game/{id64}/save.dat"))).unwrap(), "Second");
+
+        remove_dir_all(temp).unwrap();
+    }
 }
@@ -26,13 +26,37 @@ pub use xbox::XboxScanner;
 
 pub use heroic::HeroicScanner;
 
+/// Identifier of a game in the store or launcher it was installed from.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum StoreId {
+    Epic(String),
+    Gog(u64),
+    Lutris(String),
+    Steam(u32)
+}
+
 #[derive(Clone)]
 pub struct Game {
     pub name: String,
     pub installation_dir: Option<PathBuf>,
     #[cfg(unix)]
     pub prefix: Option<PathBuf>,
-    pub source: String
+    pub source: String,
+    pub store_id: Option<StoreId>
+}
+
+impl Game {
+    /// A game that wasn't found by any scanner, so only paths outside of its installation directory can be used.
+    pub fn uninstalled(name: String) -> Self {
+        Self {
+            name,
+            installation_dir: None,
+            #[cfg(unix)]
+            prefix: None,
+            source: "Uninstalled".to_owned(),
+            store_id: None
+        }
+    }
 }
 
 pub trait Scanner {
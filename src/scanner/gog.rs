@@ -1,7 +1,7 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
-use super::{Game, Scanner};
+use super::{Game, Scanner, StoreId};
 use std::fs::File;
 use std::path::{Path, PathBuf};
 
@@ -56,11 +56,13 @@ impl Scanner for GOGScanner {
                 continue;
             };
 
+            let store_id = u64::try_from(product_id).ok().map(StoreId::Gog);
+
             #[cfg(windows)]
-            games.push(Game { name: game_info.name, installation_dir: Some(dir), source: "GOG".to_owned() });
+            games.push(Game { name: game_info.name, installation_dir: Some(dir), source: "GOG".to_owned(), store_id });
 
             #[cfg(target_os = "macos")]
-            games.push(Game { name: game_info.name, installation_dir: Some(dir), prefix: None, source: "GOG".to_owned() });
+            games.push(Game { name: game_info.name, installation_dir: Some(dir), prefix: None, source: "GOG".to_owned(), store_id });
         }
 
         games
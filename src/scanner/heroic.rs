@@ -1,7 +1,8 @@
 // SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
-use super::{Game, Scanner};
+use super::{Game, Scanner, StoreId};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs::File;
 use std::path::{Path, PathBuf};
 
@@ -40,6 +41,16 @@ struct HeroicGOGManifest {
     installed: Vec<HeroicGOGGame>
 }
 
+/// Epic game installed through Legendary, which Heroic keeps in `legendaryConfig/legendary/installed.json`.
+#[derive(Deserialize)]
+struct HeroicLegendaryGame {
+    app_name: String,
+    install_path: PathBuf,
+    #[cfg(unix)]
+    platform: String,
+    title: String
+}
+
 impl HeroicScanner {
     fn get_game_name(heroic_path: &Path, game: &HeroicGOGGame) -> Option<String> {
         let manifest_path = heroic_path.join("gogdlConfig/heroic_gogdl/manifests").join(&game.app_id);
@@ -61,71 +72,131 @@ impl HeroicScanner {
         #[cfg(any(windows, target_os = "macos"))]
         None
     }
-}
-
-impl Scanner for HeroicScanner {
-    fn get_games() -> Vec<Game> {
-        let mut games = vec![];
-
-        #[cfg(all(unix, not(target_os = "macos")))]
-        let heroic_path =
-            [config().join("heroic"), home().join(".var/app/com.heroicgameslauncher.hgl")].into_iter().find(|p| p.exists());
-
-        #[cfg(target_os = "macos")]
-        let heroic_path = {
-            let path = app_data().join("heroic");
-            path.exists().then_some(path)
-        };
 
-        #[cfg(windows)]
-        let heroic_path = {
-            let path = config().join("heroic");
-            path.exists().then_some(path)
-        };
+    /// Wine prefix Heroic runs a Windows game in, Windows games without one are skipped.
+    #[cfg(unix)]
+    fn get_prefix(heroic_path: &Path, app_id: &str) -> Option<PathBuf> {
+        let game_config = heroic_path.join("GamesConfig").join(app_id).with_extension("json");
+        let game_config = serde_json::from_reader::<File, serde_json::Value>(File::open(game_config).ok()?).ok()?;
 
-        let Some(heroic_path) = heroic_path else {
-            return games;
-        };
+        game_config.get(app_id).and_then(|c| c.get("winePrefix")).and_then(|p| p.as_str()).map(Into::into)
+    }
 
+    fn get_gog_games(heroic_path: &Path, games: &mut Vec<Game>) {
         let gog_manifest = heroic_path.join("gog_store/installed.json");
 
         if !gog_manifest.exists() {
-            return games;
+            return;
         }
 
         let Ok(gog_manifest) = serde_json::from_reader::<File, HeroicGOGManifest>(File::open(gog_manifest).unwrap()) else {
             log::error!("Failed to parse GOG manifest.");
-            return games;
+            return;
         };
 
         for game in gog_manifest.installed {
-            let Some(game_name) = Self::get_game_name(&heroic_path, &game) else {
+            let Some(game_name) = Self::get_game_name(heroic_path, &game) else {
                 continue;
             };
 
             #[cfg(unix)]
             let prefix = if game.platform == "windows" {
-                let game_config = heroic_path.join("GamesConfig").join(&game.app_id).with_extension("json");
+                Self::get_prefix(heroic_path, &game.app_id)
+            } else {
+                None
+            };
+
+            #[cfg(unix)]
+            if game.platform == "windows" && prefix.is_none() {
+                continue;
+            }
+
+            let store_id = game.app_id.parse().ok().map(StoreId::Gog);
+
+            #[cfg(unix)]
+            games.push(Game {
+                name: game_name,
+                installation_dir: Some(game.install_path),
+                prefix,
+                source: "Heroic".into(),
+                store_id
+            });
+
+            #[cfg(windows)]
+            games.push(Game { name: game_name, installation_dir: Some(game.install_path), source: "Heroic".into(), store_id });
+        }
+    }
 
-                if !game_config.exists() {
-                    continue;
-                }
+    fn get_epic_games(heroic_path: &Path, games: &mut Vec<Game>) {
+        let legendary_manifest = heroic_path.join("legendaryConfig/legendary/installed.json");
 
-                let Ok(game_config) = serde_json::from_reader::<File, serde_json::Value>(File::open(game_config).unwrap()) else {
-                    continue;
-                };
+        if !legendary_manifest.exists() {
+            return;
+        }
+
+        let Ok(legendary_manifest) =
+            serde_json::from_reader::<File, HashMap<String, HeroicLegendaryGame>>(File::open(legendary_manifest).unwrap())
+        else {
+            log::error!("Failed to parse Legendary manifest.");
+            return;
+        };
 
-                game_config.get(&game.app_id).and_then(|c| c.get("winePrefix")).and_then(|p| p.as_str()).map(Into::into)
+        for game in legendary_manifest.into_values() {
+            #[cfg(unix)]
+            let prefix = if game.platform == "Windows" {
+                Self::get_prefix(heroic_path, &game.app_name)
             } else {
                 None
             };
 
             #[cfg(unix)]
-            games.push(Game { name: game_name, installation_dir: Some(game.install_path), prefix, source: "Heroic".into() });
+            if game.platform == "Windows" && prefix.is_none() {
+                continue;
+            }
+
+            let store_id = Some(StoreId::Epic(game.app_name));
+
+            #[cfg(unix)]
+            games.push(Game {
+                name: game.title,
+                installation_dir: Some(game.install_path),
+                prefix,
+                source: "Heroic".into(),
+                store_id
+            });
 
             #[cfg(windows)]
-            games.push(Game { name: game_name, installation_dir: Some(game.install_path), source: "Heroic".into() });
+            games.push(Game { name: game.title, installation_dir: Some(game.install_path), source: "Heroic".into(), store_id });
         }
+    }
+}
+
+impl Scanner for HeroicScanner {
+    fn get_games() -> Vec<Game> {
+        let mut games = vec![];
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        let heroic_path =
+            [config().join("heroic"), home().join(".var/app/com.heroicgameslauncher.hgl")].into_iter().find(|p| p.exists());
+
+        #[cfg(target_os = "macos")]
+        let heroic_path = {
+            let path = app_data().join("heroic");
+            path.exists().then_some(path)
+        };
+
+        #[cfg(windows)]
+        let heroic_path = {
+            let path = config().join("heroic");
+            path.exists().then_some(path)
+        };
+
+        let Some(heroic_path) = heroic_path else {
+            return games;
+        };
+
+        Self::get_gog_games(&heroic_path, &mut games);
+        Self::get_epic_games(&heroic_path, &mut games);
 
         games
     }
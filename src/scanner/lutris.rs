@@ -1,7 +1,7 @@
 // SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
-use super::{Game, Scanner};
+use super::{Game, Scanner, StoreId};
 use crate::dirs::{app_data, config, home};
 use std::path::PathBuf;
 
@@ -36,19 +36,21 @@ impl Scanner for LutrisScanner {
             return games;
         };
 
-        let mut stmt = con.prepare("SELECT name, directory, platform, configpath FROM games").unwrap();
+        let mut stmt = con.prepare("SELECT name, slug, directory, platform, configpath FROM games").unwrap();
         let rows = stmt
             .query_map([], |row| {
                 let name: String = row.get(0)?;
-                let path: String = row.get(1)?;
-                let platform: String = row.get(2)?;
-                let config_path: String = row.get(3)?;
-                Ok((name, PathBuf::from(path), platform, lutris_config_dir.join(&config_path).with_extension("yml")))
+                let slug: Option<String> = row.get(1)?;
+                let path: String = row.get(2)?;
+                let platform: String = row.get(3)?;
+                let config_path: String = row.get(4)?;
+                Ok((name, slug, PathBuf::from(path), platform, lutris_config_dir.join(&config_path).with_extension("yml")))
             })
             .unwrap();
 
         for row in rows {
-            let (name, dir, platform, config_file) = row.unwrap();
+            let (name, slug, dir, platform, config_file) = row.unwrap();
+            let store_id = slug.map(StoreId::Lutris);
 
             if !dir.as_os_str().is_empty() && !dir.exists() || !config_file.exists() {
                 // Lutris leaves some directories empty (not sure why) and doesn't seem to remove the directory from the database after the game is uninstalled
@@ -64,11 +66,11 @@ impl Scanner for LutrisScanner {
                     .map(PathBuf::from)
                     .map(|p| p.parent().unwrap().to_path_buf());
 
-                games.push(Game { name, installation_dir, prefix: Some(dir), source: "Lutris".into() });
+                games.push(Game { name, installation_dir, prefix: Some(dir), source: "Lutris".into(), store_id });
             } else {
                 let installation_dir = (!dir.as_os_str().is_empty()).then_some(dir);
                 // Flatpak games have their installation directory set as "" instead of None
-                games.push(Game { name, installation_dir, prefix: None, source: "Lutris".into() });
+                games.push(Game { name, installation_dir, prefix: None, source: "Lutris".into(), store_id });
             }
         }
 
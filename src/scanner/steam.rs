@@ -1,7 +1,7 @@
 // SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
-use super::{Game, Scanner};
+use super::{Game, Scanner, StoreId};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs::File;
@@ -86,7 +86,8 @@ impl Scanner for SteamScanner {
                     },
                     #[cfg(target_os = "macos")]
                     prefix: None,
-                    source: "Steam".into()
+                    source: "Steam".into(),
+                    store_id: Some(StoreId::Steam(game.app_id))
                 });
             }
         }
@@ -108,7 +109,9 @@ impl Scanner for SteamScanner {
 
                     prefix_directory.exists().then_some(prefix_directory)
                 },
-                source: "Steam".into()
+                source: "Steam".into(),
+                // Shortcuts to non-Steam games get a generated app ID that doesn't identify the game
+                store_id: None
             });
         }
 
@@ -1,9 +1,9 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 slint::include_modules!();
 
-use super::handlers::{games, settings};
+use super::handlers::{custom_games, games, settings};
 use crate::config::Config as AletheiaConfig;
 use std::cell::RefCell;
 use std::process::Command;
@@ -15,7 +15,7 @@ use crate::updater;
 pub fn run(config: &AletheiaConfig) {
     #[cfg(all(feature = "updater", not(debug_assertions)))]
     if config.check_for_updates
-        && let Ok(updater::UpdateStatus::Available(release)) = updater::check()
+        && let Ok(updater::UpdateStatus::Available(release)) = updater::check(&config.http)
     {
         let updater_window = Updater::new().unwrap();
         let updater_logic = updater_window.global::<UpdaterLogic>();
@@ -57,6 +57,7 @@ pub fn run(config: &AletheiaConfig) {
     setup_app_handlers(&app);
     games::setup(&app_weak, &cfg);
     settings::setup(&app_weak, &cfg);
+    custom_games::setup(&app_weak, &cfg);
 
     app.run().unwrap();
 }
@@ -37,11 +37,9 @@ pub fn run_first_time_setup() {
             let steam_account_id = setup_logic.get_steam_account_id();
 
             Config::save(&Config {
-                custom_databases: vec![],
                 save_dir: (&setup_logic.get_backup_path()).into(),
                 steam_account_id: (!steam_account_id.is_empty()).then(|| (&steam_account_id).into()),
-                #[cfg(feature = "updater")]
-                check_for_updates: default_config.check_for_updates
+                ..default_config.clone()
             });
 
             first_time_setup.hide().unwrap();
@@ -1,5 +1,6 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
+pub mod custom_games;
 pub mod games;
 pub mod settings;
@@ -0,0 +1,154 @@
+// SPDX-FileCopyrightText: 2026 Spencer
+// SPDX-License-Identifier: AGPL-3.0-only
+
+use crate::config::Config as AletheiaConfig;
+use crate::dirs::{PLACEHOLDERS, expand_path, glob_save_path, placeholders, unresolved_placeholder};
+use crate::gamedb::GameFiles;
+use crate::ui::app::{App, CustomGameValidation, DropdownOption, GameLogic, NotificationLogic, SettingsScreenLogic};
+use slint::{ComponentHandle, ModelRc, SharedString, VecModel};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+#[cfg(all(unix, not(target_os = "macos")))]
+const NATIVE_PLATFORM: &str = "linux";
+
+#[cfg(target_os = "macos")]
+const NATIVE_PLATFORM: &str = "mac";
+
+#[cfg(windows)]
+const NATIVE_PLATFORM: &str = "windows";
+
+pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
+    let app = app.upgrade().unwrap();
+    let settings_screen_logic = app.global::<SettingsScreenLogic>();
+
+    settings_screen_logic.on_validate_custom_game({
+        let app_weak = app.as_weak().unwrap();
+        let cfg = Rc::clone(config);
+
+        move |name, platform, path| {
+            let validation = validate(&name, &platform, &path, cfg.borrow().steam_account_id.as_deref());
+            app_weak.global::<SettingsScreenLogic>().set_custom_game_validation(validation);
+        }
+    });
+
+    settings_screen_logic.on_add_custom_game({
+        let app_weak = app.as_weak().unwrap();
+        let cfg = Rc::clone(config);
+
+        move |name, platform, path| {
+            let mut new_config = cfg.borrow().clone();
+            let entry = new_config.custom_games.entry(name.trim().to_owned()).or_default();
+
+            if let Some(paths) = platform_paths(&mut entry.files, &platform) {
+                let paths = paths.get_or_insert_with(Vec::new);
+
+                if !paths.iter().any(|existing| existing == path.trim()) {
+                    paths.push(path.trim().to_owned());
+                }
+            }
+
+            save(&app_weak, &cfg, new_config);
+            app_weak.global::<NotificationLogic>().invoke_show_success("CUSTOM_GAME_ADDED".into());
+        }
+    });
+
+    settings_screen_logic.on_remove_custom_game({
+        let app_weak = app.as_weak().unwrap();
+        let cfg = Rc::clone(config);
+
+        move |name| {
+            let mut new_config = cfg.borrow().clone();
+            new_config.custom_games.remove(name.as_str());
+
+            save(&app_weak, &cfg, new_config);
+            app_weak.global::<NotificationLogic>().invoke_show_success("CUSTOM_GAME_REMOVED".into());
+        }
+    });
+
+    let mut platforms = vec![DropdownOption { label: "Windows".into(), value: "windows".into() }];
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    platforms.insert(0, DropdownOption { label: "Linux".into(), value: "linux".into() });
+
+    #[cfg(target_os = "macos")]
+    platforms.insert(0, DropdownOption { label: "macOS".into(), value: "mac".into() });
+
+    settings_screen_logic.set_custom_game_platforms(ModelRc::new(VecModel::from(platforms)));
+    set_custom_games(&app, &config.borrow());
+}
+
+fn save(app: &App, cfg: &Rc<RefCell<AletheiaConfig>>, new_config: AletheiaConfig) {
+    AletheiaConfig::save(&new_config);
+    set_custom_games(app, &new_config);
+    *cfg.borrow_mut() = new_config;
+
+    app.global::<GameLogic>().invoke_refresh_games();
+}
+
+fn set_custom_games(app: &App, config: &AletheiaConfig) {
+    let mut names: Vec<SharedString> = config.custom_games.keys().map(Into::into).collect();
+    names.sort();
+
+    app.global::<SettingsScreenLogic>().set_custom_games(ModelRc::new(VecModel::from(names)));
+}
+
+fn platform_paths<'a>(files: &'a mut GameFiles, platform: &str) -> Option<&'a mut Option<Vec<String>>> {
+    match platform {
+        "windows" => Some(&mut files.windows),
+        #[cfg(all(unix, not(target_os = "macos")))]
+        "linux" => Some(&mut files.linux),
+        #[cfg(target_os = "macos")]
+        "mac" => Some(&mut files.mac),
+        _ => None
+    }
+}
+
+/// Checks a save path against the filesystem while the user is typing it.
+fn validate(name: &str, platform: &str, path: &str, steam_account_id: Option<&str>) -> CustomGameValidation {
+    let result = |status: &str, valid: bool| CustomGameValidation { status: status.into(), matches: 0, placeholder: "".into(), valid };
+
+    if name.trim().is_empty() {
+        return result("MISSING_NAME", false);
+    }
+
+    let path = path.trim();
+    if path.is_empty() {
+        return result("MISSING_PATH", false);
+    }
+
+    if let Some(placeholder) = placeholders(path).find(|placeholder| !PLACEHOLDERS.contains(placeholder)) {
+        return CustomGameValidation { placeholder: placeholder.into(), ..result("UNKNOWN_PLACEHOLDER", false) };
+    }
+
+    if platform != NATIVE_PLATFORM {
+        return result("OTHER_PLATFORM", true);
+    }
+
+    #[cfg(unix)]
+    let expanded = expand_path(Path::new(path), None, None, steam_account_id, false);
+
+    #[cfg(windows)]
+    let expanded = expand_path(Path::new(path), None, steam_account_id, false);
+
+    if let Some(placeholder) = unresolved_placeholder(&expanded) {
+        return CustomGameValidation { placeholder: placeholder.into(), ..result("UNCHECKABLE", true) };
+    }
+
+    #[cfg(unix)]
+    let found = glob_save_path(&expanded, None);
+
+    #[cfg(windows)]
+    let found = glob_save_path(&expanded);
+
+    let matches = found
+        .map(|paths| paths.into_iter().filter(|found| found.as_ref().is_ok_and(|found| found.is_file())).count())
+        .unwrap_or_default();
+
+    if matches == 0 {
+        result("NO_MATCHES", true)
+    } else {
+        CustomGameValidation { matches: i32::try_from(matches).unwrap_or(i32::MAX), ..result("MATCHES", true) }
+    }
+}
@@ -4,12 +4,14 @@
 use crate::archive::Error as ArchiveError;
 use crate::config::Config as AletheiaConfig;
 use crate::gamedb;
-use crate::operations::{RestoreError, backup_game, restore_game};
+use crate::operations::{BackupError, RestoreError, backup_games, default_jobs, restore_game};
+use crate::scanner::Game;
 use crate::ui::app::{App, GameLogic, GamesScreenLogic, NotificationLogic, UiGame};
 use crate::utils;
 use slint::{ComponentHandle, Model, ModelRc, VecModel};
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::thread;
 
 #[expect(clippy::too_many_lines, reason = "This is as simple as it's going to get")]
 pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
@@ -20,13 +22,14 @@ pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
 
     game_logic.on_refresh_games({
         let app_weak = app.as_weak().unwrap();
+        let cfg = Rc::clone(config);
 
         move || {
             let games_screen_logic = app_weak.global::<GamesScreenLogic>();
             let selected_games = games_screen_logic.get_selected_games();
             let select_all = selected_games.row_count() == 0;
 
-            let mut games = gamedb::get_installed_games();
+            let mut games = get_games(&cfg.borrow());
             games.sort_by_key(|a| a.name.to_lowercase());
 
             let ui_games: Vec<UiGame> = games
@@ -140,29 +143,21 @@ pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
             let cfg = cfg.as_ref().borrow();
             let notification_logic = app_weak.global::<NotificationLogic>();
             let selected_games = app_weak.global::<GamesScreenLogic>().get_selected_games();
-            let installed_games = gamedb::get_installed_games();
+            let installed_games = get_games(&cfg);
 
-            if cfg.steam_account_id.is_none() && selected_games.iter().any(|g| g.source == "Steam") {
+            // Backups cover every Steam account when none is chosen, restores need to know which account to restore to
+            if action != "backup" && cfg.steam_account_id.is_none() && selected_games.iter().any(|g| g.source == "Steam") {
                 notification_logic.invoke_show_warning("STEAM_ACCOUNT_MISSING".into());
                 return;
             }
 
             if action == "backup" {
-                let game_db = gamedb::parse();
-                let mut backed_up = 0;
-
-                for ui_game in selected_games.iter() {
-                    let game = installed_games.iter().find(|g| *g.name == *ui_game.name).unwrap();
-                    if let Err(e) = backup_game(game, &cfg, &game_db[&game.name]) {
-                        log::error!("Failed to backup {}.\n{e}", &game.name);
-                    } else {
-                        log::info!("Successfully backed up {}.", &game.name);
-                        backed_up += 1;
-                    }
-                }
+                let games = selected_games
+                    .iter()
+                    .filter_map(|ui_game| installed_games.iter().find(|g| *g.name == *ui_game.name).cloned())
+                    .collect();
 
-                app_weak.global::<GameLogic>().invoke_refresh_games();
-                notification_logic.invoke_show_success(format!("Backed up {backed_up} games").into());
+                backup(&app_weak, games, cfg.clone());
             } else {
                 if !cfg.save_dir.exists() {
                     notification_logic.invoke_show_error("BACKUP_DIRECTORY_MISSING".into());
@@ -173,21 +168,31 @@ pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
                 for ui_game in selected_games.iter() {
                     let game = installed_games.iter().find(|g| *g.name == *ui_game.name).unwrap();
 
-                    if let Err(e) = restore_game(game, &cfg) {
+                    let result = restore_game(game, &cfg, None, None);
+                    if let Err(e) = &result {
                         log::error!("Failed to restore {}: {e}", game.name);
 
-                        let error_message = if let RestoreError::Archive(ae) = &e {
-                            match ae {
+                        let error_message = match e {
+                            RestoreError::Archive(ae) => match ae {
                                 ArchiveError::ChecksumMismatch(..) | ArchiveError::FileNotFound(_) => "ARCHIVE_CORRUPTED",
                                 ArchiveError::InvalidArchive | ArchiveError::Serialization(_) => "INVALID_ARCHIVE",
                                 ArchiveError::Io(_) => "IO_ERROR",
                                 ArchiveError::UnsupportedVersion(_) => "UNSUPPORTED_ARCHIVE_VERSION"
-                            }
-                        } else {
-                            "NO_BACKUPS_FOUND"
+                            },
+                            RestoreError::Hook(_) => "HOOK_FAILED",
+                            RestoreError::Io(_) => "IO_ERROR",
+                            RestoreError::MissingAccount(_) | RestoreError::MultipleAccounts(_) => "MULTIPLE_STEAM_ACCOUNTS",
+                            RestoreError::NoBackupsFound => "NO_BACKUPS_FOUND",
+                            RestoreError::UnresolvedPlaceholder(..) => "PREFIX_REQUIRED"
                         };
 
                         notification_logic.invoke_show_error(error_message.into());
+                    } else if let Ok(report) = result
+                        && !report.skipped.is_empty()
+                    {
+                        log::warn!("Restored {} without {}", game.name, report.skipped.join(", "));
+                        notification_logic.invoke_show_error("PREFIX_REQUIRED".into());
+                        restored += 1;
                     } else {
                         log::info!("Successfully restored {}", game.name);
                         restored += 1;
@@ -204,6 +209,76 @@ pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
     game_logic.invoke_refresh_games();
 }
 
+/// Installed games followed by uninstalled games that still have saves on disk.
+fn get_games(config: &AletheiaConfig) -> Vec<Game> {
+    let mut games = gamedb::get_installed_games(config);
+    let orphaned_games = gamedb::get_orphaned_games(config, &games);
+
+    games.extend(orphaned_games);
+    games
+}
+
+/// Backs up the games on a background thread, reporting each finished game to the games screen.
+fn backup(app: &App, games: Vec<Game>, config: AletheiaConfig) {
+    let games_screen_logic = app.global::<GamesScreenLogic>();
+    let app_weak = app.as_weak();
+
+    games_screen_logic.set_busy(true);
+    games_screen_logic.set_progress_current(0);
+    games_screen_logic.set_progress_total(i32::try_from(games.len()).unwrap_or(i32::MAX));
+    games_screen_logic.set_progress_game("".into());
+
+    thread::spawn(move || {
+        let game_db = gamedb::load(&config);
+        let mut completed = 0;
+        let mut backed_up = 0;
+        let mut skipped = 0;
+        let mut verification_failed = false;
+
+        backup_games(&games, &game_db.entries, &config, default_jobs(), |game, result, _| {
+            match result {
+                Ok(report) => {
+                    log::info!("Successfully backed up {}.", &game.name);
+                    backed_up += 1;
+                    skipped += report.skipped.len();
+                }
+                Err(e) => {
+                    log::error!("Failed to backup {}.\n{e}", &game.name);
+                    verification_failed |= matches!(e, BackupError::VerificationFailed(_));
+                }
+            }
+
+            completed += 1;
+            let name = game.name.clone();
+
+            app_weak
+                .upgrade_in_event_loop(move |app| {
+                    let games_screen_logic = app.global::<GamesScreenLogic>();
+                    games_screen_logic.set_progress_current(completed);
+                    games_screen_logic.set_progress_game(name.into());
+                })
+                .ok();
+        });
+
+        app_weak
+            .upgrade_in_event_loop(move |app| {
+                let notification_logic = app.global::<NotificationLogic>();
+
+                app.global::<GamesScreenLogic>().set_busy(false);
+                app.global::<GameLogic>().invoke_refresh_games();
+
+                if verification_failed {
+                    notification_logic.invoke_show_error("BACKUP_VERIFICATION_FAILED".into());
+                } else if skipped > 0 {
+                    notification_logic.invoke_show_warning(format!("Backed up {backed_up} games, skipped {skipped} files").into());
+                } else {
+                    notification_logic.invoke_show_success(format!("Backed up {backed_up} games").into());
+                }
+            })
+            .ok();
+    });
+}
+
 #[expect(clippy::cast_precision_loss, reason = "Only used for UI")]
 fn format_size(size: u64) -> String {
     const KB: u64 = 1024;
@@ -1,4 +1,4 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
 use crate::config::Config as AletheiaConfig;
@@ -45,12 +45,15 @@ pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
                 custom_databases: ui_cfg.custom_databases.iter().map(Into::into).collect(),
                 save_dir: (&ui_cfg.save_dir).into(),
                 steam_account_id: (!ui_cfg.steam_account_id.is_empty()).then(|| (&ui_cfg.steam_account_id).into()),
+                verify_backups: ui_cfg.verify_backups,
                 #[cfg(feature = "updater")]
-                check_for_updates: ui_cfg.check_for_updates
+                check_for_updates: ui_cfg.check_for_updates,
+                ..cfg.borrow().clone()
             };
 
             settings_logic.set_previous_save_dir(ui_cfg.save_dir);
             settings_logic.set_previous_steam_account_id(ui_cfg.steam_account_id);
+            settings_logic.set_previous_verify_backups(ui_cfg.verify_backups);
             settings_logic.set_previous_check_for_updates(ui_cfg.check_for_updates);
 
             AletheiaConfig::save(&new_config);
@@ -81,25 +84,7 @@ pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
         }
     });
 
-    settings_screen_logic.on_update_gamedb({
-        let app_weak = app.as_weak().unwrap();
-
-        move || {
-            let notification_logic = app_weak.global::<NotificationLogic>();
-
-            match gamedb::update() {
-                Ok(true) => {
-                    app_weak.global::<GameLogic>().invoke_refresh_games();
-                    notification_logic.invoke_show_success("GAMEDB_UPDATED".into());
-                }
-                Ok(false) => notification_logic.invoke_show_info("GAMEDB_UP_TO_DATE".into()),
-                Err(e) => {
-                    notification_logic.invoke_show_error("GAMEDB_UPDATE_FAILED".into());
-                    log::error!("Error updating GameDB: {e}");
-                }
-            }
-        }
-    });
+    setup_gamedb(&app, config);
 
     let config_ref = config.borrow();
     let steam_account_id = get_steam_id(&config_ref);
@@ -118,6 +103,7 @@ pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
         custom_databases: ModelRc::new(config_ref.custom_databases.iter().map(Into::into).collect::<VecModel<_>>()),
         save_dir: config_ref.save_dir.to_string_lossy().as_ref().into(),
         steam_account_id: steam_account_id_str.into(),
+        verify_backups: config_ref.verify_backups,
         #[cfg(feature = "updater")]
         check_for_updates: config_ref.check_for_updates,
         #[cfg(not(feature = "updater"))]
@@ -126,6 +112,7 @@ pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
 
     settings_screen_logic.set_previous_save_dir(config_ref.save_dir.to_string_lossy().as_ref().into());
     settings_screen_logic.set_previous_steam_account_id(steam_account_id_str.into());
+    settings_screen_logic.set_previous_verify_backups(config_ref.verify_backups);
 
     #[cfg(feature = "updater")]
     {
@@ -136,6 +123,60 @@ pub fn setup(app: &slint::Weak<App>, config: &Rc<RefCell<AletheiaConfig>>) {
     settings_screen_logic.invoke_get_steam_users();
 }
 
+/// Handlers for replacing the official game database, from a download or a file.
+fn setup_gamedb(app: &App, config: &Rc<RefCell<AletheiaConfig>>) {
+    let settings_screen_logic = app.global::<SettingsScreenLogic>();
+
+    settings_screen_logic.on_update_gamedb({
+        let app_weak = app.as_weak().unwrap();
+        let cfg = Rc::clone(config);
+
+        move || {
+            let notification_logic = app_weak.global::<NotificationLogic>();
+
+            match gamedb::update(&cfg.borrow()) {
+                Ok(true) => {
+                    app_weak.global::<GameLogic>().invoke_refresh_games();
+                    notification_logic.invoke_show_success("GAMEDB_UPDATED".into());
+                }
+                Ok(false) => notification_logic.invoke_show_info("GAMEDB_UP_TO_DATE".into()),
+                Err(e) => {
+                    notification_logic.invoke_show_error("GAMEDB_UPDATE_FAILED".into());
+                    log::error!("Error updating GameDB: {e}");
+                }
+            }
+        }
+    });
+
+    settings_screen_logic.on_import_gamedb({
+        let app_weak = app.as_weak();
+
+        move || {
+            let app = app_weak.upgrade().unwrap();
+
+            slint::spawn_local(async move {
+                let Some(file) = rfd::AsyncFileDialog::new().add_filter("GameDB", &["yaml", "yml"]).pick_file().await else {
+                    return;
+                };
+
+                let notification_logic = app.global::<NotificationLogic>();
+
+                match gamedb::import(file.path()) {
+                    Ok(()) => {
+                        app.global::<GameLogic>().invoke_refresh_games();
+                        notification_logic.invoke_show_success("GAMEDB_IMPORTED".into());
+                    }
+                    Err(e) => {
+                        notification_logic.invoke_show_error("GAMEDB_IMPORT_FAILED".into());
+                        log::error!("Error importing GameDB: {e}");
+                    }
+                }
+            })
+            .unwrap();
+        }
+    });
+}
+
 fn get_steam_id(config: &AletheiaConfig) -> Option<String> {
     let users = SteamScanner::get_users()?;
 
@@ -1,13 +1,14 @@
 // SPDX-FileCopyrightText: 2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
-use crate::archive::{ArchiveReader, Error as ArchiveError};
+use crate::archive::{ArchiveReader, Error as ArchiveError, FileEntry, steam_accounts};
 use crate::config::Config as AletheiaConfig;
 use crate::gamedb;
-use crate::operations::{RestoreError, restore_game};
+use crate::operations::{RestoreError, restore_game, source_account};
+use crate::scanner::{Game, SteamScanner};
 use crate::ui::app::RestoreDialog;
-use crate::ui::app::RestoreLogic;
-use slint::ComponentHandle;
+use crate::ui::app::{DropdownOption, RestoreLogic};
+use slint::{ComponentHandle, ModelRc, SharedString, VecModel};
 use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
@@ -25,6 +26,28 @@ pub fn run_restore_dialog(config: &AletheiaConfig, archive_path: &str) {
     let cfg = Rc::new(RefCell::new(config.clone()));
     let restore_dialog = RestoreDialog::new().unwrap();
     let restore_logic = restore_dialog.global::<RestoreLogic>();
+    let installed = gamedb::get_installed_games(config).iter().any(|g| g.name == reader.game);
+    let files = Rc::new(reader.files);
+
+    let steam_account_options = steam_account_options(&files);
+    let source_steam_account_options = source_steam_account_options(&files, &steam_account_options);
+
+    restore_logic.on_browse_prefix({
+        let restore_weak = restore_dialog.as_weak();
+
+        move || {
+            let restore_weak = restore_weak.clone();
+
+            slint::spawn_local(async move {
+                if let Some(folder) = rfd::AsyncFileDialog::new().set_directory(crate::dirs::home()).pick_folder().await
+                    && let Some(restore_dialog) = restore_weak.upgrade()
+                {
+                    restore_dialog.global::<RestoreLogic>().set_prefix(SharedString::from(folder.path().to_string_lossy().as_ref()));
+                }
+            })
+            .unwrap();
+        }
+    });
 
     restore_logic.on_cancel({
         let restore_weak = restore_dialog.as_weak().unwrap();
@@ -34,37 +57,156 @@ pub fn run_restore_dialog(config: &AletheiaConfig, archive_path: &str) {
         }
     });
 
+    restore_logic.on_select_steam_account({
+        let restore_weak = restore_dialog.as_weak().unwrap();
+        let files = Rc::clone(&files);
+        let options = steam_account_options.clone();
+
+        move |steam_account| {
+            let restore_logic = restore_weak.global::<RestoreLogic>();
+            restore_logic.set_steam_account(steam_account);
+            update_remapped_from(&restore_logic, &files, &options);
+        }
+    });
+
+    restore_logic.on_select_source_steam_account({
+        let restore_weak = restore_dialog.as_weak().unwrap();
+        let files = Rc::clone(&files);
+        let options = steam_account_options.clone();
+
+        move |steam_account| {
+            let restore_logic = restore_weak.global::<RestoreLogic>();
+            restore_logic.set_source_steam_account(steam_account);
+            update_remapped_from(&restore_logic, &files, &options);
+        }
+    });
+
     restore_logic.on_restore({
         let restore_weak = restore_dialog.as_weak().unwrap();
 
         move || {
             let restore_logic = restore_weak.global::<RestoreLogic>();
-            let installed_games = gamedb::get_installed_games();
+            let installed_games = gamedb::get_installed_games(&cfg.borrow());
             let game_name = restore_logic.get_game_name();
             let game_name = game_name.as_str();
 
-            let Some(game) = installed_games.iter().find(|g| g.name == game_name) else {
-                restore_logic.set_error("GAME_NOT_INSTALLED".into());
-                return;
-            };
+            let game = installed_games
+                .into_iter()
+                .find(|g| g.name == game_name)
+                .unwrap_or_else(|| uninstalled_game(game_name, &restore_logic.get_prefix()));
 
-            if let Err(RestoreError::Archive(e)) = restore_game(game, &cfg.borrow()) {
-                let error_message = match e {
-                    ArchiveError::ChecksumMismatch(..) | ArchiveError::FileNotFound(_) => "ARCHIVE_CORRUPTED",
-                    ArchiveError::InvalidArchive | ArchiveError::Serialization(_) => "INVALID_ARCHIVE",
-                    ArchiveError::Io(_) => "IO_ERROR",
-                    ArchiveError::UnsupportedVersion(_) => "UNSUPPORTED_ARCHIVE_VERSION"
-                };
+            let steam_account = restore_logic.get_steam_account();
+            let steam_account = (!steam_account.is_empty()).then_some(steam_account.as_str());
+            let from_steam_account = restore_logic.get_source_steam_account();
+            let from_steam_account = (!from_steam_account.is_empty()).then_some(from_steam_account.as_str());
 
-                restore_logic.set_error(error_message.into());
-            } else {
+            let result = restore_game(&game, &cfg.borrow(), steam_account, from_steam_account);
+            let skipped = match &result {
+                Ok(report) => report.skipped.clone(),
+                Err(RestoreError::UnresolvedPlaceholder(_, skipped)) => skipped.clone(),
+                Err(_) => vec![]
+            };
+            restore_logic.set_skipped(skipped.join("\n").into());
+
+            if let Err(e) = result {
+                restore_logic.set_error(error_key(&e).into());
+            } else if skipped.is_empty() {
                 restore_weak.hide().unwrap();
+            } else {
+                // The other saves were restored, the dialog stays open to list the skipped ones and allow choosing a prefix
+                restore_logic.set_error("PREFIX_REQUIRED".into());
             }
         }
     });
 
+    restore_logic.set_show_prefix(cfg!(unix) && !installed);
     restore_logic.set_game_name(reader.game.into());
+    restore_logic.set_steam_account_options(ModelRc::new(VecModel::from(steam_account_options)));
+    restore_logic.set_source_steam_account_options(ModelRc::new(VecModel::from(source_steam_account_options)));
+    restore_logic.invoke_select_steam_account(config.steam_account_id.clone().unwrap_or_default().into());
     slint::set_xdg_app_id("moe.spencer.Aletheia").unwrap();
 
     restore_dialog.run().unwrap();
 }
+
+/// Accounts on this PC to restore to, only offered for archives with Steam account specific saves.
+fn steam_account_options(files: &[FileEntry]) -> Vec<DropdownOption> {
+    if steam_accounts(files).is_empty() {
+        return vec![];
+    }
+
+    let mut options: Vec<DropdownOption> = SteamScanner::get_users()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(steam_id, user)| {
+            let id3 = SteamScanner::id64_to_id3(steam_id.parse::<u64>().ok()?);
+            Some(DropdownOption { label: user.persona_name.into(), value: id3.to_string().into() })
+        })
+        .collect();
+
+    options.sort_by(|a, b| a.label.cmp(&b.label));
+    options
+}
+
+/// Accounts in the archive to restore from, so the user can pick whose saves to restore from a backup of a shared PC.
+fn source_steam_account_options(files: &[FileEntry], steam_account_options: &[DropdownOption]) -> Vec<DropdownOption> {
+    let accounts = steam_accounts(files);
+    if accounts.len() <= 1 {
+        return vec![];
+    }
+
+    accounts
+        .into_iter()
+        .map(|account| {
+            let label = steam_account_options.iter().find(|option| option.value == account).map(|option| &option.label);
+            DropdownOption {
+                label: label.map_or_else(|| account.into(), |label| format!("{label} ({account})").into()),
+                value: account.into()
+            }
+        })
+        .collect()
+}
+
+/// Shows which account the saves come from when they're restored to a different one.
+fn update_remapped_from(restore_logic: &RestoreLogic, files: &[FileEntry], options: &[DropdownOption]) {
+    let steam_account = restore_logic.get_steam_account();
+    let from_steam_account = restore_logic.get_source_steam_account();
+    let target = Some(steam_account.as_str()).filter(|id| !id.is_empty());
+
+    let remapped_from = match source_account(files, target, Some(from_steam_account.as_str()).filter(|id| !id.is_empty())) {
+        Ok(Some(source)) if Some(source) != target => options
+            .iter()
+            .find(|option| option.value == source)
+            .map_or_else(|| source.into(), |option| format!("{} ({source})", option.label).into()),
+        _ => SharedString::new()
+    };
+
+    restore_logic.set_remapped_from(remapped_from);
+}
+
+/// Key of the message `RestoreLogic` shows for a failed restore.
+fn error_key(error: &RestoreError) -> &'static str {
+    match error {
+        RestoreError::Archive(ae) => match ae {
+            ArchiveError::ChecksumMismatch(..) | ArchiveError::FileNotFound(_) => "ARCHIVE_CORRUPTED",
+            ArchiveError::InvalidArchive | ArchiveError::Serialization(_) => "INVALID_ARCHIVE",
+            ArchiveError::Io(_) => "IO_ERROR",
+            ArchiveError::UnsupportedVersion(_) => "UNSUPPORTED_ARCHIVE_VERSION"
+        },
+        RestoreError::Hook(_) => "HOOK_FAILED",
+        RestoreError::Io(_) => "IO_ERROR",
+        RestoreError::MissingAccount(_) | RestoreError::MultipleAccounts(_) => "MULTIPLE_STEAM_ACCOUNTS",
+        RestoreError::NoBackupsFound => "NO_BACKUPS_FOUND",
+        RestoreError::UnresolvedPlaceholder(..) => "PREFIX_REQUIRED"
+    }
+}
+
+#[cfg(unix)]
+fn uninstalled_game(name: &str, prefix: &str) -> Game {
+    Game { prefix: (!prefix.is_empty()).then(|| prefix.into()), ..Game::uninstalled(name.to_owned()) }
+}
+
+#[cfg(windows)]
+fn uninstalled_game(name: &str, _prefix: &str) -> Game {
+    Game::uninstalled(name.to_owned())
+}
@@ -1,12 +1,9 @@
-// SPDX-FileCopyrightText: 2025 Spencer
+// SPDX-FileCopyrightText: 2025-2026 Spencer
 // SPDX-License-Identifier: AGPL-3.0-only
 
-use reqwest::{blocking::Client, header};
+use crate::http::{self, HttpClient, HttpConfig};
 use semver::Version;
 
-const RELEASES_URL: &str = "https://api.github.com/repos/Spencer-0003/aletheia/releases";
-const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
-
 #[derive(Clone, serde::Deserialize)]
 pub struct Release {
     pub body: String,
@@ -22,6 +19,8 @@ pub struct Release {
 pub enum Error {
     #[error("Failed to deserialize: {0}")]
     Deserialize(#[from] serde_json::Error),
+    #[error(transparent)]
+    Http(#[from] http::Error),
     #[error("Network error: {0}")]
     Network(#[from] reqwest::Error)
 }
@@ -33,9 +32,9 @@ pub enum UpdateStatus {
     Available(Release)
 }
 
-pub fn check() -> Result<UpdateStatus> {
-    let client = Client::new();
-    let response = client.get(RELEASES_URL).header(header::USER_AGENT, USER_AGENT).send()?.error_for_status()?;
+pub fn check(config: &HttpConfig) -> Result<UpdateStatus> {
+    let client = HttpClient::new(config)?;
+    let response = client.get(&config.releases_url).send()?.error_for_status()?;
 
     let releases: Vec<Release> = serde_json::from_reader(response)?;
     let Some(latest_release) = releases.into_iter().find(|r| !r.pre_release) else {